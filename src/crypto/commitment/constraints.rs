@@ -1,20 +1,35 @@
 use ark_ff::Field;
-use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget};
 use ark_relations::r1cs::SynthesisError;
 use ark_std::vec::Vec;
 
-use super::BatchCommitmentScheme;
+use super::{BatchCommitmentScheme, CommitmentScheme, HomomorphicCommitmentScheme};
+
+pub trait CommitmentGadget<C: CommitmentScheme, ConstraintF: Field> {
+    type ScalarVar: Clone;
+    type CommitmentVar: AllocVar<C::Commitment, ConstraintF> + EqGadget<ConstraintF> + Clone;
+
+    /// Recompute the commitment to `scalars` under the (constant) `committing_key`
+    fn commit(
+        committing_key: &[C::Base],
+        scalars: &[Self::ScalarVar],
+    ) -> Result<Self::CommitmentVar, SynthesisError>;
+}
 
 pub trait BatchCommitmentGadget<C: BatchCommitmentScheme, ConstraintF: Field> {
     type ScalarVar: AllocVar<C::Scalar, ConstraintF> + Clone;
     type ChallengeVar: AllocVar<C::Challenge, ConstraintF> + Clone;
 
+    /// Aggregate the messages of a batch, each padded with zeros up to `width`
+    /// Fails with `SynthesisError::Unsatisfiable` on an empty batch or an oversized message
     fn aggregate(
         commitments: Vec<Vec<Self::ScalarVar>>,
+        width: usize,
         tau: Self::ChallengeVar,
         initial: Option<Self::ChallengeVar>,
-    ) -> (Vec<Self::ScalarVar>, Self::ChallengeVar);
+    ) -> Result<(Vec<Self::ScalarVar>, Self::ChallengeVar), SynthesisError>;
 
+    /// The width of the batch is the length of `aggregation`
     fn enforce_equal(
         aggregation: Vec<Self::ScalarVar>,
         commitments: Vec<Vec<Self::ScalarVar>>,
@@ -22,3 +37,20 @@ pub trait BatchCommitmentGadget<C: BatchCommitmentScheme, ConstraintF: Field> {
         initial: Option<Self::ChallengeVar>,
     ) -> Result<(), SynthesisError>;
 }
+
+pub trait HomomorphicCommitmentGadget<C: HomomorphicCommitmentScheme, ConstraintF: Field> {
+    type ScalarVar: AllocVar<C::Scalar, ConstraintF> + Clone;
+
+    /// new = old + delta, padding the shorter one with zeros
+    fn update(
+        old: Vec<Self::ScalarVar>,
+        delta: Vec<Self::ScalarVar>,
+    ) -> Result<Vec<Self::ScalarVar>, SynthesisError>;
+
+    /// Enforce that the committed values satisfy new = old + delta
+    fn enforce_update(
+        new: Vec<Self::ScalarVar>,
+        old: Vec<Self::ScalarVar>,
+        delta: Vec<Self::ScalarVar>,
+    ) -> Result<(), SynthesisError>;
+}
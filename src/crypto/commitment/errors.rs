@@ -0,0 +1,55 @@
+use ark_std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchCommitmentError {
+    /// The batch does not contain any commitment
+    EmptyBatch,
+    /// The `index`-th message is longer than the declared width of the batch
+    Oversized {
+        index: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl fmt::Display for BatchCommitmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyBatch => write!(f, "empty batch"),
+            Self::Oversized { index, len, width } => write!(
+                f,
+                "message {} has length {} exceeding the batch width {}",
+                index, len, width
+            ),
+        }
+    }
+}
+
+impl ark_std::error::Error for BatchCommitmentError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// All `capacity` leaves of the registry are in use
+    Full { capacity: usize },
+    /// No commitment has been registered at `index`
+    UnknownIndex { index: usize, len: usize },
+    /// The depth of the registry must be at least one
+    InvalidDepth,
+    /// The underlying hash or Merkle tree failed
+    Hash,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full { capacity } => write!(f, "registry is full ({} commitments)", capacity),
+            Self::UnknownIndex { index, len } => {
+                write!(f, "no commitment at index {} ({} registered)", index, len)
+            }
+            Self::InvalidDepth => write!(f, "registry depth must be at least one"),
+            Self::Hash => write!(f, "merkle tree hashing failed"),
+        }
+    }
+}
+
+impl ark_std::error::Error for RegistryError {}
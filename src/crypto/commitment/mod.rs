@@ -1,10 +1,16 @@
 pub mod constraints;
-pub use constraints::BatchCommitmentGadget;
+pub use constraints::{BatchCommitmentGadget, CommitmentGadget, HomomorphicCommitmentGadget};
+
+pub mod errors;
+pub use errors::{BatchCommitmentError, RegistryError};
 
 pub mod pedersen;
+pub mod registry;
 
 use ark_std::vec::Vec;
 
+use crate::crypto::protocol::transcript::Context;
+
 pub trait CommitmentScheme {
     type Scalar;
     type Base;
@@ -14,30 +20,73 @@ pub trait CommitmentScheme {
 }
 
 /// The basic functionality for a Batch Commitment Scheme.
+/// Messages in a batch may have different lengths (ragged batches); shorter messages are
+/// implicitly padded with zeros up to the width of the batch.
 pub trait BatchCommitmentScheme: CommitmentScheme {
     type Challenge;
 
     // [cm_0, cm_1, ... cm_k, proof dependent cm]
+    // Fails if a message is longer than the batch key
     fn batch_commit(
         batch_key: &[Self::Base],
         commitments: &[&[Self::Scalar]],
-    ) -> Vec<Self::Commitment>;
+    ) -> Result<Vec<Self::Commitment>, BatchCommitmentError>;
 
     fn challenge(
         public_inputs: &[Self::Scalar],
         commitments: &[Self::Base],
         proof_dependent_commitment: &Self::Base,
+    ) -> Self::Challenge {
+        Self::challenge_with_context(public_inputs, commitments, proof_dependent_commitment, None)
+    }
+
+    /// `challenge` with `context` hashed ahead of the inputs, binding the batch to it
+    fn challenge_with_context(
+        public_inputs: &[Self::Scalar],
+        commitments: &[Self::Base],
+        proof_dependent_commitment: &Self::Base,
+        context: Option<&Context>,
     ) -> Self::Challenge;
 
     fn aggregate(
         commitments: &[Self::Commitment],
         tau: Self::Challenge,
         initial: Option<Self::Challenge>,
-    ) -> (Self::Commitment, Self::Challenge);
+    ) -> Result<(Self::Commitment, Self::Challenge), BatchCommitmentError>;
 
+    /// Aggregate the messages of a batch, each padded with zeros up to `width`
     fn scalar_aggregate(
         commitments: &[&[Self::Scalar]],
+        width: usize,
         tau: Self::Challenge,
         initial: Option<Self::Challenge>,
-    ) -> (Vec<Self::Scalar>, Self::Challenge);
+    ) -> Result<(Vec<Self::Scalar>, Self::Challenge), BatchCommitmentError>;
+}
+
+/// An additively homomorphic commitment scheme, where a committed state can be
+/// updated by committing only to the difference.
+pub trait HomomorphicCommitmentScheme: CommitmentScheme {
+    /// cm(m_0) + cm(m_1) = cm(m_0 + m_1)
+    fn add(lhs: &Self::Commitment, rhs: &Self::Commitment) -> Self::Commitment;
+
+    /// cm(m_0) - cm(m_1) = cm(m_0 - m_1)
+    fn sub(lhs: &Self::Commitment, rhs: &Self::Commitment) -> Self::Commitment;
+
+    /// Add two openings, padding the shorter one with zeros
+    fn add_openings(lhs: &[Self::Scalar], rhs: &[Self::Scalar]) -> Vec<Self::Scalar>;
+
+    /// Subtract two openings, padding the shorter one with zeros
+    fn sub_openings(lhs: &[Self::Scalar], rhs: &[Self::Scalar]) -> Vec<Self::Scalar>;
+
+    /// Map signed integers (e.g. balance changes) to scalars
+    fn signed_delta(delta: &[i64]) -> Vec<Self::Scalar>;
+
+    /// Apply `delta` to the committed state `(commitment, opening)`
+    /// Returns the updated commitment and opening
+    fn update(
+        committing_key: &[Self::Base],
+        commitment: &Self::Commitment,
+        opening: &[Self::Scalar],
+        delta: &[Self::Scalar],
+    ) -> (Self::Commitment, Vec<Self::Scalar>);
 }
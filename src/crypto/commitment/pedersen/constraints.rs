@@ -1,36 +1,57 @@
 use std::{fmt::Debug, marker::PhantomData};
 
-use ark_ec::CurveGroup;
-use ark_r1cs_std::{boolean::Boolean, fields::FieldVar};
+use ark_ec::{
+    twisted_edwards::{Projective as TEProjective, TECurveConfig},
+    AffineRepr, CurveGroup, Group,
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    groups::{curves::twisted_edwards::AffineVar, CurveVar},
+};
 use ark_relations::r1cs::SynthesisError;
-use ark_std::ops::Add;
+use ark_std::{cmp::max, ops::Add};
 
-use crate::crypto::commitment::constraints::BatchCommitmentGadget;
+use crate::crypto::commitment::constraints::{
+    BatchCommitmentGadget, CommitmentGadget, HomomorphicCommitmentGadget,
+};
 
 use super::Pedersen;
 
-pub struct PedersenGadget<C, FV>
+/// Gadget over the committed scalars of `Pedersen<C>`.
+/// `ConstraintF` defaults to the scalar field of `C` (e.g. `FpVar`); any other constraint field
+/// requires an emulated field variable (e.g. `NonNativeFieldVar<C::ScalarField, ConstraintF>`).
+pub struct PedersenGadget<C, FV, ConstraintF = <C as Group>::ScalarField>
 where
     C: CurveGroup,
-    FV: FieldVar<C::ScalarField, C::ScalarField>,
+    ConstraintF: PrimeField,
+    FV: FieldVar<C::ScalarField, ConstraintF>,
 {
     _group: PhantomData<C>,
     _field: PhantomData<FV>,
+    _constraint_field: PhantomData<ConstraintF>,
 }
 
-impl<C, FV> BatchCommitmentGadget<Pedersen<C>, C::ScalarField> for PedersenGadget<C, FV>
+impl<C, FV, ConstraintF> BatchCommitmentGadget<Pedersen<C>, ConstraintF>
+    for PedersenGadget<C, FV, ConstraintF>
 where
     C: CurveGroup,
-    FV: FieldVar<C::ScalarField, C::ScalarField> + Clone + Debug + Add,
+    ConstraintF: PrimeField,
+    FV: FieldVar<C::ScalarField, ConstraintF> + Clone + Debug + Add,
 {
     type ScalarVar = FV;
     type ChallengeVar = FV;
 
     fn aggregate(
         commitments: Vec<Vec<Self::ScalarVar>>,
+        width: usize,
         tau: Self::ChallengeVar,
         initial: Option<Self::ChallengeVar>,
-    ) -> (Vec<Self::ScalarVar>, Self::ChallengeVar) {
+    ) -> Result<(Vec<Self::ScalarVar>, Self::ChallengeVar), SynthesisError> {
+        if commitments.is_empty() || commitments.iter().any(|cm| cm.len() > width) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
         let mut powers_of_tau = vec![];
         let mut cur = initial.unwrap_or(tau.clone());
         for _ in 0..commitments.len() {
@@ -38,21 +59,18 @@ where
             cur *= &tau;
         }
 
-        let len = commitments[0].len();
-        let indicies = 0..len;
-
-        let aggregation = indicies
+        // shorter messages are zero-padded up to `width`
+        let aggregation = (0..width)
             .map(|c| {
                 commitments
                     .iter()
                     .zip(powers_of_tau.iter())
-                    .fold(Self::ScalarVar::zero(), |acc, (cm, tau)| {
-                        acc + cm[c].clone() * tau
-                    })
+                    .filter_map(|(cm, tau)| cm.get(c).map(|m| m.clone() * tau))
+                    .fold(Self::ScalarVar::zero(), |acc, term| acc + term)
             })
             .collect::<Vec<Self::ScalarVar>>();
 
-        (aggregation, cur)
+        Ok((aggregation, cur))
     }
 
     fn enforce_equal(
@@ -61,36 +79,94 @@ where
         tau: Self::ChallengeVar,
         initial: Option<Self::ChallengeVar>,
     ) -> Result<(), SynthesisError> {
-        let mut powers_of_tau = vec![];
-        let mut cur = initial.unwrap_or(tau.clone());
-        for _ in 0..commitments.len() {
-            powers_of_tau.push(cur.clone());
-            cur *= &tau;
-        }
-
-        let len = commitments[0].len();
-        let indicies = 0..len;
-
-        let _aggregation = indicies
-            .map(|c| {
-                commitments
-                    .iter()
-                    .zip(powers_of_tau.iter())
-                    .fold(Self::ScalarVar::zero(), |acc, (cm, tau)| {
-                        acc + cm[c].clone() * tau
-                    })
-            })
-            .collect::<Vec<Self::ScalarVar>>();
+        let (_aggregation, _) = Self::aggregate(commitments, aggregation.len(), tau, initial)?;
 
         _aggregation
             .iter()
             .zip(aggregation.iter())
-            .for_each(|(_aggr, aggr)| {
-                _aggr
-                    .conditional_enforce_equal(aggr, &Boolean::constant(true))
-                    .unwrap()
-            });
+            .try_for_each(|(_aggr, aggr)| _aggr.enforce_equal(aggr))
+    }
+}
+
+impl<C, FV, ConstraintF> HomomorphicCommitmentGadget<Pedersen<C>, ConstraintF>
+    for PedersenGadget<C, FV, ConstraintF>
+where
+    C: CurveGroup,
+    ConstraintF: PrimeField,
+    FV: FieldVar<C::ScalarField, ConstraintF> + Clone + Debug + Add,
+{
+    type ScalarVar = FV;
+
+    fn update(
+        old: Vec<Self::ScalarVar>,
+        delta: Vec<Self::ScalarVar>,
+    ) -> Result<Vec<Self::ScalarVar>, SynthesisError> {
+        let zero = Self::ScalarVar::zero();
+        let updated = (0..max(old.len(), delta.len()))
+            .map(|i| old.get(i).unwrap_or(&zero).clone() + delta.get(i).unwrap_or(&zero))
+            .collect();
+
+        Ok(updated)
+    }
+
+    fn enforce_update(
+        new: Vec<Self::ScalarVar>,
+        old: Vec<Self::ScalarVar>,
+        delta: Vec<Self::ScalarVar>,
+    ) -> Result<(), SynthesisError> {
+        let updated = Self::update(old, delta)?;
+
+        let zero = Self::ScalarVar::zero();
+        (0..max(new.len(), updated.len())).try_for_each(|i| {
+            new.get(i)
+                .unwrap_or(&zero)
+                .enforce_equal(updated.get(i).unwrap_or(&zero))
+        })
+    }
+}
+
+/// Pedersen commitment over a twisted Edwards curve embedded in the constraint field
+/// (e.g. BabyJubJub over BN254, Jubjub over BLS12-381)
+pub struct EmbeddedPedersenGadget<P>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    _curve: PhantomData<P>,
+}
+
+impl<P> CommitmentGadget<Pedersen<TEProjective<P>>, P::BaseField> for EmbeddedPedersenGadget<P>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    type ScalarVar = FpVar<P::BaseField>;
+    type CommitmentVar = AffineVar<P, FpVar<P::BaseField>>;
+
+    fn commit(
+        committing_key: &[<TEProjective<P> as CurveGroup>::Affine],
+        scalars: &[Self::ScalarVar],
+    ) -> Result<Self::CommitmentVar, SynthesisError> {
+        if scalars.len() > committing_key.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // [g, 2g, 4g, ...] for each generator, one per bit of the scalar
+        let num_bits = P::BaseField::MODULUS_BIT_SIZE as usize;
+        let bases = committing_key[..scalars.len()]
+            .iter()
+            .map(|g| {
+                let mut cur = g.into_group();
+                (0..num_bits)
+                    .map(|_| {
+                        let base = cur;
+                        cur.double_in_place();
+                        base
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
 
-        Ok(())
+        Self::CommitmentVar::precomputed_base_multiscalar_mul_le(&bases, scalars.iter())
     }
 }
@@ -1,14 +1,18 @@
 pub mod constraints;
-pub use constraints::PedersenGadget;
+pub use constraints::{EmbeddedPedersenGadget, PedersenGadget};
 
 use std::marker::PhantomData;
 
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::{BigInteger, Field, PrimeField};
-use ark_std::{vec::Vec, Zero};
+use ark_std::{cmp::max, vec::Vec, Zero};
 use sha3::{Digest, Keccak256};
 
-use super::{BatchCommitmentScheme, CommitmentScheme};
+use crate::crypto::protocol::transcript::{Context, Encoding};
+
+use super::{
+    BatchCommitmentError, BatchCommitmentScheme, CommitmentScheme, HomomorphicCommitmentScheme,
+};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -32,26 +36,61 @@ impl<C: CurveGroup> CommitmentScheme for Pedersen<C> {
     }
 }
 
+impl<C: CurveGroup> Pedersen<C>
+where
+    C::BaseField: PrimeField,
+{
+    /// Commit to elements of the base field, reduced modulo the group order.
+    /// Native counterpart of `EmbeddedPedersenGadget`, where the messages are circuit variables.
+    pub fn commit_base_field(committing_key: &[C::Affine], messages: &[C::BaseField]) -> C::Affine {
+        let scalars = cfg_iter!(messages)
+            .map(|m| C::ScalarField::from_le_bytes_mod_order(&m.into_bigint().to_bytes_le()))
+            .collect::<Vec<_>>();
+        Self::commit(committing_key, &scalars)
+    }
+}
+
+/// Check that the batch is not empty and no message exceeds `width`
+fn check_batch<T>(commitments: &[&[T]], width: usize) -> Result<(), BatchCommitmentError> {
+    if commitments.is_empty() {
+        return Err(BatchCommitmentError::EmptyBatch);
+    }
+    match commitments.iter().position(|cm| cm.len() > width) {
+        Some(index) => Err(BatchCommitmentError::Oversized {
+            index,
+            len: commitments[index].len(),
+            width,
+        }),
+        None => Ok(()),
+    }
+}
+
 impl<C: CurveGroup> BatchCommitmentScheme for Pedersen<C> {
     type Challenge = C::ScalarField;
 
     fn batch_commit(
         batch_key: &[Self::Base],
         commitments: &[&[Self::Scalar]],
-    ) -> Vec<Self::Commitment> {
+    ) -> Result<Vec<Self::Commitment>, BatchCommitmentError> {
+        check_batch(commitments, batch_key.len())?;
+
         let commitments_g1 = cfg_iter!(commitments)
             .map(|cm| Pedersen::<C>::commit(batch_key, cm))
             .collect::<Vec<Self::Base>>();
 
-        commitments_g1
+        Ok(commitments_g1)
     }
 
-    fn challenge(
+    fn challenge_with_context(
         public_inputs: &[Self::Scalar],
         commitments: &[Self::Commitment],
         proof_dependent_commitment: &Self::Base,
+        context: Option<&Context>,
     ) -> Self::Challenge {
         let mut hasher = Keccak256::new();
+        if let Some(context) = context {
+            hasher.update(context.to_bytes(Encoding::Evm));
+        }
         let mut strings = vec![];
 
         let mut update = |s: &String| {
@@ -87,7 +126,11 @@ impl<C: CurveGroup> BatchCommitmentScheme for Pedersen<C> {
         commitments: &[Self::Commitment],
         tau: Self::Challenge,
         initial: Option<Self::Challenge>,
-    ) -> (Self::Commitment, Self::Challenge) {
+    ) -> Result<(Self::Commitment, Self::Challenge), BatchCommitmentError> {
+        if commitments.is_empty() {
+            return Err(BatchCommitmentError::EmptyBatch);
+        }
+
         let mut powers_of_tau = vec![];
         let mut cur = initial.unwrap_or(tau);
         for _ in 0..commitments.len() {
@@ -95,17 +138,20 @@ impl<C: CurveGroup> BatchCommitmentScheme for Pedersen<C> {
             cur *= &tau;
         }
 
-        (
+        Ok((
             C::msm_bigint(&commitments[..], &powers_of_tau[..]).into_affine(),
             cur, // next initial
-        )
+        ))
     }
 
     fn scalar_aggregate(
         commitments: &[&[Self::Scalar]],
+        width: usize,
         tau: Self::Challenge,
         initial: Option<Self::Challenge>,
-    ) -> (Vec<Self::Scalar>, Self::Challenge) {
+    ) -> Result<(Vec<Self::Scalar>, Self::Challenge), BatchCommitmentError> {
+        check_batch(commitments, width)?;
+
         // powers_of_tau = [t, ..., t^(max_degree + 1)], len = commitments.len()
         let mut powers_of_tau = vec![];
         let mut cur = initial.unwrap_or(tau);
@@ -114,19 +160,70 @@ impl<C: CurveGroup> BatchCommitmentScheme for Pedersen<C> {
             cur *= &tau;
         }
 
-        let len = commitments[0].len();
-        let indicies = (0..len).collect::<Vec<usize>>();
+        // shorter messages are zero-padded up to `width`
+        let indicies = (0..width).collect::<Vec<usize>>();
 
-        (
+        Ok((
             cfg_iter!(indicies)
                 .map(|c| {
                     cfg_iter!(commitments)
                         .zip(cfg_iter!(powers_of_tau))
-                        .map(|(cm, tau)| cm[*c] * tau)
+                        .filter_map(|(cm, tau)| cm.get(*c).map(|m| *m * tau))
                         .sum()
                 })
                 .collect(),
             cur, // next initial
+        ))
+    }
+}
+
+impl<C: CurveGroup> HomomorphicCommitmentScheme for Pedersen<C> {
+    fn add(lhs: &Self::Commitment, rhs: &Self::Commitment) -> Self::Commitment {
+        (*lhs + rhs).into_affine()
+    }
+
+    fn sub(lhs: &Self::Commitment, rhs: &Self::Commitment) -> Self::Commitment {
+        (lhs.into_group() - rhs).into_affine()
+    }
+
+    fn add_openings(lhs: &[Self::Scalar], rhs: &[Self::Scalar]) -> Vec<Self::Scalar> {
+        let zero = Self::Scalar::zero();
+        (0..max(lhs.len(), rhs.len()))
+            .map(|i| *lhs.get(i).unwrap_or(&zero) + rhs.get(i).unwrap_or(&zero))
+            .collect()
+    }
+
+    fn sub_openings(lhs: &[Self::Scalar], rhs: &[Self::Scalar]) -> Vec<Self::Scalar> {
+        let zero = Self::Scalar::zero();
+        (0..max(lhs.len(), rhs.len()))
+            .map(|i| *lhs.get(i).unwrap_or(&zero) - rhs.get(i).unwrap_or(&zero))
+            .collect()
+    }
+
+    fn signed_delta(delta: &[i64]) -> Vec<Self::Scalar> {
+        delta
+            .iter()
+            .map(|d| {
+                let abs = Self::Scalar::from(d.unsigned_abs());
+                if d.is_negative() {
+                    -abs
+                } else {
+                    abs
+                }
+            })
+            .collect()
+    }
+
+    fn update(
+        committing_key: &[Self::Base],
+        commitment: &Self::Commitment,
+        opening: &[Self::Scalar],
+        delta: &[Self::Scalar],
+    ) -> (Self::Commitment, Vec<Self::Scalar>) {
+        let delta_commitment = Self::commit(committing_key, delta);
+        (
+            Self::add(commitment, &delta_commitment),
+            Self::add_openings(opening, delta),
         )
     }
 }
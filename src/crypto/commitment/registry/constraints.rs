@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use ark_crypto_primitives::{
+    crh::poseidon::constraints::{CRHGadget, CRHParametersVar, TwoToOneCRHGadget},
+    merkle_tree::{
+        constraints::{ConfigGadget, PathVar},
+        IdentityDigestConverter,
+    },
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ec::twisted_edwards::TECurveConfig;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::curves::twisted_edwards::AffineVar,
+};
+use ark_relations::r1cs::SynthesisError;
+
+use super::RegistryConfig;
+
+/// In-circuit membership proof of a commitment in a `Registry`
+pub type MembershipProofVar<F> = PathVar<RegistryConfig<F>, F, RegistryConfigGadget<F>>;
+
+pub struct RegistryConfigGadget<F: PrimeField + Absorb> {
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> ConfigGadget<RegistryConfig<F>, F> for RegistryConfigGadget<F> {
+    type Leaf = [FpVar<F>];
+    type LeafDigest = FpVar<F>;
+    type LeafInnerConverter = IdentityDigestConverter<FpVar<F>>;
+    type InnerDigest = FpVar<F>;
+    type LeafHash = CRHGadget<F>;
+    type TwoToOneHash = TwoToOneCRHGadget<F>;
+}
+
+/// Membership of commitments over a twisted Edwards curve embedded in the constraint field,
+/// e.g. the output of `EmbeddedPedersenGadget`
+pub struct RegistryGadget<P>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField + Absorb,
+{
+    _curve: PhantomData<P>,
+}
+
+impl<P> RegistryGadget<P>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField + Absorb,
+{
+    /// Whether `commitment` is registered under `root`
+    pub fn is_member(
+        params: &PoseidonConfig<P::BaseField>,
+        root: &FpVar<P::BaseField>,
+        commitment: &AffineVar<P, FpVar<P::BaseField>>,
+        proof: &MembershipProofVar<P::BaseField>,
+    ) -> Result<Boolean<P::BaseField>, SynthesisError> {
+        let params = CRHParametersVar {
+            parameters: params.clone(),
+        };
+        // same encoding as `Registry::leaf`, where the identity `(0, 1)` is `(0, 0)`
+        let identity = commitment.x.is_zero()?.and(&commitment.y.is_one()?)?;
+        let y = identity.select(&FpVar::zero(), &commitment.y)?;
+        let leaf = [FpVar::one(), commitment.x.clone(), y];
+
+        proof.verify_membership(&params, &params, root, &leaf[..])
+    }
+
+    pub fn enforce_membership(
+        params: &PoseidonConfig<P::BaseField>,
+        root: &FpVar<P::BaseField>,
+        commitment: &AffineVar<P, FpVar<P::BaseField>>,
+        proof: &MembershipProofVar<P::BaseField>,
+    ) -> Result<(), SynthesisError> {
+        Self::is_member(params, root, commitment, proof)?.enforce_equal(&Boolean::TRUE)
+    }
+}
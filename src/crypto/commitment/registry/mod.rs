@@ -0,0 +1,154 @@
+pub mod constraints;
+pub use constraints::{MembershipProofVar, RegistryConfigGadget, RegistryGadget};
+
+use std::marker::PhantomData;
+
+use ark_crypto_primitives::{
+    crh::poseidon::{TwoToOneCRH, CRH},
+    merkle_tree::{Config, IdentityDigestConverter, MerkleTree, Path},
+    sponge::{
+        poseidon::{find_poseidon_ark_and_mds, PoseidonConfig},
+        Absorb,
+    },
+};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, Zero};
+use ark_std::vec::Vec;
+
+use super::RegistryError;
+
+/// Membership proof of a commitment in a `Registry`
+pub type MembershipProof<F> = Path<RegistryConfig<F>>;
+
+/// Poseidon Merkle tree over a prime field
+pub struct RegistryConfig<F: PrimeField + Absorb> {
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField + Absorb> Config for RegistryConfig<F> {
+    type Leaf = [F];
+    type LeafDigest = F;
+    type LeafInnerDigestConverter = IdentityDigestConverter<F>;
+    type InnerDigest = F;
+    type LeafHash = CRH<F>;
+    type TwoToOneHash = TwoToOneCRH<F>;
+}
+
+/// Poseidon parameters of width 3 (rate 2, capacity 1) with the x^5 S-box,
+/// valid for fields where x^5 is a permutation (e.g. the scalar fields of BN254, BLS12-381)
+pub fn poseidon_config<F: PrimeField>() -> PoseidonConfig<F> {
+    let (full_rounds, partial_rounds, alpha, rate) = (8, 57, 5, 2);
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        rate,
+        full_rounds as u64,
+        partial_rounds as u64,
+        0,
+    );
+    PoseidonConfig::new(full_rounds, partial_rounds, alpha, mds, ark, rate, 1)
+}
+
+/// Append-only registry of commitments, kept as a fixed-depth Merkle tree whose leaves are
+/// the Poseidon hashes of `[1, x, y]` for each commitment `(x, y)`.
+/// The leading domain tag keeps a leaf from being confused with an inner node.
+pub struct Registry<C: CurveGroup>
+where
+    C::BaseField: PrimeField + Absorb,
+{
+    pub params: PoseidonConfig<C::BaseField>,
+    tree: MerkleTree<RegistryConfig<C::BaseField>>,
+    commitments: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> Registry<C>
+where
+    C::BaseField: PrimeField + Absorb,
+{
+    /// Empty registry with room for `2^depth` commitments
+    pub fn new(params: PoseidonConfig<C::BaseField>, depth: usize) -> Result<Self, RegistryError> {
+        if depth == 0 {
+            return Err(RegistryError::InvalidDepth);
+        }
+        let tree =
+            MerkleTree::blank(&params, &params, depth + 1).map_err(|_| RegistryError::Hash)?;
+
+        Ok(Self {
+            params,
+            tree,
+            commitments: vec![],
+        })
+    }
+
+    /// Leaf preimage of a commitment; the identity is encoded as `(0, 0)`, which is on no
+    /// curve, also in-circuit where it is `(0, 1)`, see `RegistryGadget::is_member`
+    pub fn leaf(commitment: &C::Affine) -> [C::BaseField; 3] {
+        let (x, y) = commitment
+            .xy()
+            .map(|(x, y)| (*x, *y))
+            .unwrap_or((C::BaseField::zero(), C::BaseField::zero()));
+        [C::BaseField::one(), x, y]
+    }
+
+    pub fn capacity(&self) -> usize {
+        1 << (self.tree.height() - 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.commitments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commitments.is_empty()
+    }
+
+    pub fn root(&self) -> C::BaseField {
+        self.tree.root()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&C::Affine> {
+        self.commitments.get(index)
+    }
+
+    /// Register a commitment and return its index
+    pub fn insert(&mut self, commitment: &C::Affine) -> Result<usize, RegistryError> {
+        let index = self.commitments.len();
+        if index == self.capacity() {
+            return Err(RegistryError::Full {
+                capacity: self.capacity(),
+            });
+        }
+
+        self.tree
+            .update(index, &Self::leaf(commitment))
+            .map_err(|_| RegistryError::Hash)?;
+        self.commitments.push(*commitment);
+
+        Ok(index)
+    }
+
+    /// Membership proof of the commitment registered at `index`
+    pub fn prove(&self, index: usize) -> Result<MembershipProof<C::BaseField>, RegistryError> {
+        if index >= self.commitments.len() {
+            return Err(RegistryError::UnknownIndex {
+                index,
+                len: self.commitments.len(),
+            });
+        }
+
+        self.tree
+            .generate_proof(index)
+            .map_err(|_| RegistryError::Hash)
+    }
+
+    /// Check that `commitment` is registered under `root`
+    pub fn verify(
+        params: &PoseidonConfig<C::BaseField>,
+        root: &C::BaseField,
+        commitment: &C::Affine,
+        proof: &MembershipProof<C::BaseField>,
+    ) -> bool {
+        proof
+            .verify(params, params, root, Self::leaf(commitment))
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,109 @@
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+
+use super::{Channel, ChannelError};
+use crate::crypto::protocol::transcript::TranscriptProtocol;
+
+/// Channel of a non-interactive proof: the messages of the prover are written to the proof
+/// and absorbed into the transcript, and every challenge is derived from the transcript.
+/// The prover and the verifier each hold their own `FiatShamir`, over the same proof.
+#[derive(Clone)]
+pub struct FiatShamir<T: TranscriptProtocol> {
+    transcript: T,
+    proof: Vec<u8>,
+    position: usize,
+}
+
+impl<T: TranscriptProtocol> FiatShamir<T> {
+    /// Prover side, starting with an empty proof
+    pub fn new(transcript: T) -> Self {
+        Self {
+            transcript,
+            proof: vec![],
+            position: 0,
+        }
+    }
+
+    /// Verifier side, reading the messages from `proof`
+    pub fn with_proof(transcript: T, proof: Vec<u8>) -> Self {
+        Self {
+            transcript,
+            proof,
+            position: 0,
+        }
+    }
+
+    pub fn into_proof(self) -> Vec<u8> {
+        self.proof
+    }
+
+    /// Check that the verifier read the whole proof
+    pub fn finish(self) -> Result<T, ChannelError> {
+        if self.position != self.proof.len() {
+            return Err(ChannelError::TrailingMessages);
+        }
+        Ok(self.transcript)
+    }
+
+    fn serialize<M: CanonicalSerialize>(message: &M) -> Result<Vec<u8>, ChannelError> {
+        let mut bytes = vec![];
+        message
+            .serialize_compressed(&mut bytes)
+            .map_err(|_| ChannelError::Serialization)?;
+        Ok(bytes)
+    }
+}
+
+impl<T: TranscriptProtocol> Channel for FiatShamir<T> {
+    fn bind<M: CanonicalSerialize>(
+        &mut self,
+        label: &'static [u8],
+        message: &M,
+    ) -> Result<(), ChannelError> {
+        let bytes = Self::serialize(message)?;
+        self.transcript.append(label, &bytes);
+        Ok(())
+    }
+
+    fn send<M: CanonicalSerialize>(
+        &mut self,
+        label: &'static [u8],
+        message: &M,
+    ) -> Result<(), ChannelError> {
+        let bytes = Self::serialize(message)?;
+        self.transcript.append(label, &bytes);
+        self.proof.extend(bytes);
+        Ok(())
+    }
+
+    fn receive<M: CanonicalDeserialize>(
+        &mut self,
+        label: &'static [u8],
+    ) -> Result<M, ChannelError> {
+        let mut reader = &self.proof[self.position..];
+        let message =
+            M::deserialize_compressed(&mut reader).map_err(|_| ChannelError::Serialization)?;
+        let end = self.proof.len() - reader.len();
+        self.transcript
+            .append(label, &self.proof[self.position..end]);
+        self.position = end;
+        Ok(message)
+    }
+
+    /// The challenge is derived from the transcript, so `rng` is not used
+    fn send_challenge<F: PrimeField, R: RngCore>(
+        &mut self,
+        label: &'static [u8],
+        _rng: &mut R,
+    ) -> Result<F, ChannelError> {
+        Ok(self.transcript.challenge_scalar(label))
+    }
+
+    fn receive_challenge<F: PrimeField>(
+        &mut self,
+        label: &'static [u8],
+    ) -> Result<F, ChannelError> {
+        Ok(self.transcript.challenge_scalar(label))
+    }
+}
@@ -0,0 +1,92 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+
+use super::{Channel, ChannelError};
+
+type Queue = Rc<RefCell<VecDeque<(&'static [u8], Vec<u8>)>>>;
+
+/// One end of an in-memory channel, where messages are serialized into a queue
+pub struct MemoryChannel {
+    incoming: Queue,
+    outgoing: Queue,
+}
+
+impl MemoryChannel {
+    /// Both ends of a channel, e.g. for the prover and the verifier
+    pub fn pair() -> (Self, Self) {
+        let (left, right) = (Queue::default(), Queue::default());
+        (
+            Self {
+                incoming: left.clone(),
+                outgoing: right.clone(),
+            },
+            Self {
+                incoming: right,
+                outgoing: left,
+            },
+        )
+    }
+
+    /// Number of messages sent to this end and not received yet
+    pub fn pending(&self) -> usize {
+        self.incoming.borrow().len()
+    }
+}
+
+impl Channel for MemoryChannel {
+    /// Both parties already know the public data
+    fn bind<M: CanonicalSerialize>(&mut self, _: &'static [u8], _: &M) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    fn send<M: CanonicalSerialize>(
+        &mut self,
+        label: &'static [u8],
+        message: &M,
+    ) -> Result<(), ChannelError> {
+        let mut bytes = vec![];
+        message
+            .serialize_compressed(&mut bytes)
+            .map_err(|_| ChannelError::Serialization)?;
+        self.outgoing.borrow_mut().push_back((label, bytes));
+        Ok(())
+    }
+
+    fn receive<M: CanonicalDeserialize>(
+        &mut self,
+        label: &'static [u8],
+    ) -> Result<M, ChannelError> {
+        let (found, bytes) = self
+            .incoming
+            .borrow_mut()
+            .pop_front()
+            .ok_or(ChannelError::Empty)?;
+        if found != label {
+            return Err(ChannelError::UnexpectedMessage {
+                expected: label,
+                found,
+            });
+        }
+        M::deserialize_compressed(&bytes[..]).map_err(|_| ChannelError::Serialization)
+    }
+
+    fn send_challenge<F: PrimeField, R: RngCore>(
+        &mut self,
+        label: &'static [u8],
+        rng: &mut R,
+    ) -> Result<F, ChannelError> {
+        let challenge = F::rand(rng);
+        self.send(label, &challenge)?;
+        Ok(challenge)
+    }
+
+    fn receive_challenge<F: PrimeField>(
+        &mut self,
+        label: &'static [u8],
+    ) -> Result<F, ChannelError> {
+        self.receive(label)
+    }
+}
@@ -0,0 +1,134 @@
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt, rand::RngCore};
+
+pub mod fiat_shamir;
+pub mod memory;
+pub use fiat_shamir::FiatShamir;
+pub use memory::MemoryChannel;
+
+/// Typed messages between the prover and the verifier of an interactive protocol.
+///
+/// The verifier draws its challenges with `send_challenge`, and the prover reads them with
+/// `receive_challenge`. Over `FiatShamir` both derive the challenges from a transcript instead,
+/// which turns the interactive protocol into a non-interactive one.
+pub trait Channel {
+    /// Public data known to both parties, e.g. the instance, which only a transcript absorbs
+    fn bind<M: CanonicalSerialize>(
+        &mut self,
+        label: &'static [u8],
+        message: &M,
+    ) -> Result<(), ChannelError>;
+
+    fn send<M: CanonicalSerialize>(
+        &mut self,
+        label: &'static [u8],
+        message: &M,
+    ) -> Result<(), ChannelError>;
+
+    fn receive<M: CanonicalDeserialize>(&mut self, label: &'static [u8])
+        -> Result<M, ChannelError>;
+
+    /// Verifier side: choose a challenge with `rng` and send it to the prover
+    fn send_challenge<F: PrimeField, R: RngCore>(
+        &mut self,
+        label: &'static [u8],
+        rng: &mut R,
+    ) -> Result<F, ChannelError>;
+
+    /// Prover side: the challenge chosen by the verifier
+    fn receive_challenge<F: PrimeField>(&mut self, label: &'static [u8])
+        -> Result<F, ChannelError>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChannelError {
+    /// No message is waiting to be received
+    Empty,
+    /// The next message does not carry the expected label
+    UnexpectedMessage {
+        expected: &'static [u8],
+        found: &'static [u8],
+    },
+    /// A message could not be serialized or deserialized
+    Serialization,
+    /// Messages are left over once the protocol is over
+    TrailingMessages,
+}
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no message to receive"),
+            Self::UnexpectedMessage { expected, found } => write!(
+                f,
+                "expected message {}, found {}",
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(found)
+            ),
+            Self::Serialization => write!(f, "failed to (de)serialize a message"),
+            Self::TrailingMessages => write!(f, "messages left over"),
+        }
+    }
+}
+
+impl ark_std::error::Error for ChannelError {}
+
+/// Whether a party is done, with its output
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status<O> {
+    Continue,
+    Done(O),
+}
+
+/// One side of an interactive protocol, as a state machine moving once per `step`.
+/// A step receives the messages of the other party, if any, and sends the answer.
+pub trait Party {
+    type Output;
+    type Error: From<ChannelError>;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<Self::Output>, Self::Error>;
+}
+
+/// Run two parties to completion, alternating their steps and starting with `first`
+pub fn run<A, B, ChA, ChB>(
+    first: &mut A,
+    first_channel: &mut ChA,
+    second: &mut B,
+    second_channel: &mut ChB,
+) -> Result<(A::Output, B::Output), A::Error>
+where
+    A: Party,
+    B: Party<Error = A::Error>,
+    ChA: Channel,
+    ChB: Channel,
+{
+    let (mut first_output, mut second_output) = (None, None);
+    while first_output.is_none() || second_output.is_none() {
+        if first_output.is_none() {
+            if let Status::Done(output) = first.step(first_channel)? {
+                first_output = Some(output);
+            }
+        }
+        if second_output.is_none() {
+            if let Status::Done(output) = second.step(second_channel)? {
+                second_output = Some(output);
+            }
+        }
+    }
+
+    Ok((first_output.unwrap(), second_output.unwrap()))
+}
+
+/// Run a party on its own, which only terminates over a channel answering by itself
+/// such as `FiatShamir`
+pub fn run_alone<P: Party, Ch: Channel>(
+    party: &mut P,
+    channel: &mut Ch,
+) -> Result<P::Output, P::Error> {
+    loop {
+        if let Status::Done(output) = party.step(channel)? {
+            return Ok(output);
+        }
+    }
+}
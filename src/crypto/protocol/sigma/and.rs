@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use ark_std::rand::{CryptoRng, RngCore};
+
+use super::{prove_with_phases, verify_with_phases, SigmaProtocol, TranscriptProtocol};
+
+/// Proof of knowledge of witnesses for both instances.
+/// Both protocols run in parallel and answer the same challenge.
+pub struct And<P1, P2> {
+    _protocols: PhantomData<(P1, P2)>,
+}
+
+impl<P1, P2> SigmaProtocol for And<P1, P2>
+where
+    P1: SigmaProtocol,
+    P2: SigmaProtocol<Challenge = P1::Challenge, Error = P1::Error>,
+{
+    type PublicParameters = (P1::PublicParameters, P2::PublicParameters);
+    type Instance = (P1::Instance, P2::Instance);
+    type Witness = (P1::Witness, P2::Witness);
+    type Proof = (Self::Commitment, Self::Response);
+
+    type Statement = (P1::Statement, P2::Statement);
+    type Commitment = (P1::Commitment, P2::Commitment);
+    type State = (P1::State, P2::State);
+    type Challenge = P1::Challenge;
+    type Response = (P1::Response, P2::Response);
+
+    type Error = P1::Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
+        Ok((P1::setup(&pp.0)?, P2::setup(&pp.1)?))
+    }
+
+    fn prove<T: TranscriptProtocol, R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        witness: &Self::Witness,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        prove_with_phases::<Self, T, R>(pp, instance, witness, transcript, rng)
+    }
+
+    fn verify<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> Result<bool, Self::Error> {
+        verify_with_phases::<Self, T>(pp, instance, proof, transcript)
+    }
+
+    fn bind<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        Ok((
+            P1::bind(&pp.0, &instance.0, transcript)?,
+            P2::bind(&pp.1, &instance.1, transcript)?,
+        ))
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        let (state1, commitment1) = P1::commit(&pp.0, &statement.0, &witness.0, rng)?;
+        let (state2, commitment2) = P2::commit(&pp.1, &statement.1, &witness.1, rng)?;
+
+        Ok(((state1, state2), (commitment1, commitment2)))
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        P1::absorb(&commitment.0, transcript)?;
+        P2::absorb(&commitment.1, transcript)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        P1::challenge(transcript)
+    }
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        Ok((
+            P1::respond(&pp.0, &statement.0, &witness.0, state.0, challenge)?,
+            P2::respond(&pp.1, &statement.1, &witness.1, state.1, challenge)?,
+        ))
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        Ok(
+            P1::check(&pp.0, &statement.0, &commitment.0, challenge, &response.0)?
+                && P2::check(&pp.1, &statement.1, &commitment.1, challenge, &response.1)?,
+        )
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        let (commitment1, response1) = P1::simulate(&pp.0, &statement.0, challenge, rng)?;
+        let (commitment2, response2) = P2::simulate(&pp.1, &statement.1, challenge, rng)?;
+
+        Ok(((commitment1, commitment2), (response1, response2)))
+    }
+}
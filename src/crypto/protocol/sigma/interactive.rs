@@ -0,0 +1,137 @@
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+
+use super::SigmaProtocol;
+use crate::crypto::protocol::channel::{Channel, ChannelError, Party, Status};
+
+enum ProverState<S> {
+    Start,
+    Committed(S),
+    Done,
+}
+
+/// Prover of `P` over a channel, for a statement both parties agreed on.
+/// It moves first, sending the commitment, and then the response to the challenge.
+pub struct SigmaProver<'a, P: SigmaProtocol, R> {
+    pp: &'a P::PublicParameters,
+    statement: P::Statement,
+    witness: &'a P::Witness,
+    rng: R,
+    state: ProverState<P::State>,
+}
+
+impl<'a, P: SigmaProtocol, R> SigmaProver<'a, P, R> {
+    pub fn new(
+        pp: &'a P::PublicParameters,
+        statement: P::Statement,
+        witness: &'a P::Witness,
+        rng: R,
+    ) -> Self {
+        Self {
+            pp,
+            statement,
+            witness,
+            rng,
+            state: ProverState::Start,
+        }
+    }
+}
+
+impl<'a, P, R> Party for SigmaProver<'a, P, R>
+where
+    P: SigmaProtocol,
+    P::Statement: CanonicalSerialize,
+    P::Commitment: CanonicalSerialize,
+    P::Challenge: PrimeField,
+    P::Response: CanonicalSerialize,
+    P::Error: From<ChannelError>,
+    R: RngCore + CryptoRng,
+{
+    type Output = ();
+    type Error = P::Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<()>, P::Error> {
+        match std::mem::replace(&mut self.state, ProverState::Done) {
+            ProverState::Start => {
+                channel.bind(b"statement", &self.statement)?;
+                let (state, commitment) =
+                    P::commit(self.pp, &self.statement, self.witness, &mut self.rng)?;
+                channel.send(b"commitment", &commitment)?;
+                self.state = ProverState::Committed(state);
+                Ok(Status::Continue)
+            }
+            ProverState::Committed(state) => {
+                let challenge = channel.receive_challenge(b"challenge")?;
+                let response =
+                    P::respond(self.pp, &self.statement, self.witness, state, &challenge)?;
+                channel.send(b"response", &response)?;
+                Ok(Status::Done(()))
+            }
+            ProverState::Done => Ok(Status::Done(())),
+        }
+    }
+}
+
+enum VerifierState<M, E> {
+    Start,
+    Challenged(M, E),
+    Done(bool),
+}
+
+/// Verifier of `P` over a channel, drawing its challenge with `rng`.
+/// It accepts once the response to its challenge checks out.
+pub struct SigmaVerifier<'a, P: SigmaProtocol, R> {
+    pp: &'a P::PublicParameters,
+    statement: P::Statement,
+    rng: R,
+    state: VerifierState<P::Commitment, P::Challenge>,
+}
+
+impl<'a, P: SigmaProtocol, R> SigmaVerifier<'a, P, R> {
+    pub fn new(pp: &'a P::PublicParameters, statement: P::Statement, rng: R) -> Self {
+        Self {
+            pp,
+            statement,
+            rng,
+            state: VerifierState::Start,
+        }
+    }
+}
+
+impl<'a, P, R> Party for SigmaVerifier<'a, P, R>
+where
+    P: SigmaProtocol,
+    P::Statement: CanonicalSerialize,
+    P::Commitment: CanonicalDeserialize,
+    P::Challenge: PrimeField,
+    P::Response: CanonicalDeserialize,
+    P::Error: From<ChannelError>,
+    R: RngCore + CryptoRng,
+{
+    type Output = bool;
+    type Error = P::Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<bool>, P::Error> {
+        match std::mem::replace(&mut self.state, VerifierState::Done(false)) {
+            VerifierState::Start => {
+                channel.bind(b"statement", &self.statement)?;
+                let commitment = channel.receive(b"commitment")?;
+                let challenge = channel.send_challenge(b"challenge", &mut self.rng)?;
+                self.state = VerifierState::Challenged(commitment, challenge);
+                Ok(Status::Continue)
+            }
+            VerifierState::Challenged(commitment, challenge) => {
+                let response = channel.receive(b"response")?;
+                let accepted =
+                    P::check(self.pp, &self.statement, &commitment, &challenge, &response)?;
+                self.state = VerifierState::Done(accepted);
+                Ok(Status::Done(accepted))
+            }
+            VerifierState::Done(accepted) => {
+                self.state = VerifierState::Done(accepted);
+                Ok(Status::Done(accepted))
+            }
+        }
+    }
+}
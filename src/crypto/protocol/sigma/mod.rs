@@ -1,16 +1,38 @@
-use super::transcript::TranscriptProtocol;
+pub mod and;
+pub mod interactive;
+pub mod or;
+pub use and::And;
+pub use interactive::{SigmaProver, SigmaVerifier};
+pub use or::{Either, Or};
+
+use super::transcript::{bind_context, Context, TranscriptProtocol};
 use ark_std::rand::{CryptoRng, RngCore};
 
 /// Sigma protocol trait for zero-knowledge proof systems.
+/// `verify` returns `Ok(false)` on an invalid proof, and an error only if the inputs are malformed.
+///
+/// Besides the non-interactive `prove` and `verify`, the three moves of the protocol are exposed:
+/// `bind` the instance to the transcript, `commit`, `absorb` the commitment, derive the `challenge`,
+/// `respond`, and `check` the conversation. `simulate` produces an accepting conversation for a
+/// given challenge without the witness, which is what the OR composition relies on.
 pub trait SigmaProtocol {
     type PublicParameters;
     type Instance;
     type Witness;
     type Proof;
 
-    type Error: 'static;
+    /// Instance after it is bound to the transcript
+    type Statement;
+    /// First message of the prover
+    type Commitment;
+    /// Secret state of the prover between `commit` and `respond`
+    type State;
+    type Challenge;
+    type Response;
+
+    type Error: ark_std::error::Error + 'static;
 
-    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, ()>;
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error>;
 
     fn prove<T: TranscriptProtocol, R: RngCore + CryptoRng>(
         pp: &Self::PublicParameters,
@@ -18,12 +40,112 @@ pub trait SigmaProtocol {
         witness: &Self::Witness,
         transcript: &mut T,
         rng: &mut R,
-    ) -> Result<Self::Proof, ()>;
+    ) -> Result<Self::Proof, Self::Error>;
 
     fn verify<T: TranscriptProtocol>(
         pp: &Self::PublicParameters,
         instance: &Self::Instance,
         proof: &Self::Proof,
         transcript: &mut T,
-    ) -> Result<bool, ()>;
+    ) -> Result<bool, Self::Error>;
+
+    /// `prove` with `context` absorbed before the instance, so that the proof is
+    /// a signature of knowledge on it and only verifies with the same context
+    fn prove_with_context<T: TranscriptProtocol, R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        witness: &Self::Witness,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        bind_context(context, transcript);
+        Self::prove(pp, instance, witness, transcript, rng)
+    }
+
+    fn verify_with_context<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        proof: &Self::Proof,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<bool, Self::Error> {
+        bind_context(context, transcript);
+        Self::verify(pp, instance, proof, transcript)
+    }
+
+    fn bind<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error>;
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error>;
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error>;
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge;
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error>;
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error>;
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error>;
+}
+
+/// Fiat-Shamir proof `(commitment, response)` built from the phases of `P`
+pub fn prove_with_phases<P: SigmaProtocol, T: TranscriptProtocol, R: RngCore + CryptoRng>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    transcript: &mut T,
+    rng: &mut R,
+) -> Result<(P::Commitment, P::Response), P::Error> {
+    let statement = P::bind(pp, instance, transcript)?;
+    let (state, commitment) = P::commit(pp, &statement, witness, rng)?;
+    P::absorb(&commitment, transcript)?;
+    let challenge = P::challenge(transcript);
+    let response = P::respond(pp, &statement, witness, state, &challenge)?;
+
+    Ok((commitment, response))
+}
+
+/// Verify a proof made by `prove_with_phases`
+pub fn verify_with_phases<P: SigmaProtocol, T: TranscriptProtocol>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    proof: &(P::Commitment, P::Response),
+    transcript: &mut T,
+) -> Result<bool, P::Error> {
+    let (commitment, response) = proof;
+    let statement = P::bind(pp, instance, transcript)?;
+    P::absorb(commitment, transcript)?;
+    let challenge = P::challenge(transcript);
+
+    P::check(pp, &statement, commitment, &challenge, response)
 }
@@ -0,0 +1,189 @@
+use std::marker::PhantomData;
+
+use ark_ff::{Field, UniformRand};
+use ark_std::rand::{CryptoRng, RngCore};
+
+use super::{prove_with_phases, verify_with_phases, SigmaProtocol, TranscriptProtocol};
+
+/// Witness for one side of a composition
+#[derive(Clone, Debug, PartialEq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Proof of knowledge of a witness for one of the two instances, without revealing which one
+/// (Cramer, Damgård and Schoenmakers). The prover simulates the branch it has no witness for,
+/// and the two challenges must sum up to the challenge of the transcript.
+pub struct Or<P1, P2> {
+    _protocols: PhantomData<(P1, P2)>,
+}
+
+type OrState<P1, P2> = Either<
+    (
+        <P1 as SigmaProtocol>::State,
+        <P1 as SigmaProtocol>::Witness,
+        <P1 as SigmaProtocol>::Challenge,
+        <P2 as SigmaProtocol>::Response,
+    ),
+    (
+        <P2 as SigmaProtocol>::State,
+        <P2 as SigmaProtocol>::Witness,
+        <P1 as SigmaProtocol>::Challenge,
+        <P1 as SigmaProtocol>::Response,
+    ),
+>;
+
+impl<P1, P2> SigmaProtocol for Or<P1, P2>
+where
+    P1: SigmaProtocol,
+    P2: SigmaProtocol<Challenge = P1::Challenge, Error = P1::Error>,
+    P1::Witness: Clone,
+    P2::Witness: Clone,
+    P1::Challenge: Field,
+{
+    type PublicParameters = (P1::PublicParameters, P2::PublicParameters);
+    type Instance = (P1::Instance, P2::Instance);
+    type Witness = Either<P1::Witness, P2::Witness>;
+    type Proof = (Self::Commitment, Self::Response);
+
+    type Statement = (P1::Statement, P2::Statement);
+    type Commitment = (P1::Commitment, P2::Commitment);
+    /// The real state and witness, with the challenge and response of the simulated branch
+    type State = OrState<P1, P2>;
+    type Challenge = P1::Challenge;
+    /// The challenge of the left branch, and the responses of both branches
+    type Response = (P1::Challenge, P1::Response, P2::Response);
+
+    type Error = P1::Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
+        Ok((P1::setup(&pp.0)?, P2::setup(&pp.1)?))
+    }
+
+    fn prove<T: TranscriptProtocol, R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        witness: &Self::Witness,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        prove_with_phases::<Self, T, R>(pp, instance, witness, transcript, rng)
+    }
+
+    fn verify<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> Result<bool, Self::Error> {
+        verify_with_phases::<Self, T>(pp, instance, proof, transcript)
+    }
+
+    fn bind<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        Ok((
+            P1::bind(&pp.0, &instance.0, transcript)?,
+            P2::bind(&pp.1, &instance.1, transcript)?,
+        ))
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        let simulated_challenge = P1::Challenge::rand(rng);
+        match witness {
+            Either::Left(witness) => {
+                let (state, commitment1) = P1::commit(&pp.0, &statement.0, witness, rng)?;
+                let (commitment2, response2) =
+                    P2::simulate(&pp.1, &statement.1, &simulated_challenge, rng)?;
+                Ok((
+                    Either::Left((state, witness.clone(), simulated_challenge, response2)),
+                    (commitment1, commitment2),
+                ))
+            }
+            Either::Right(witness) => {
+                let (commitment1, response1) =
+                    P1::simulate(&pp.0, &statement.0, &simulated_challenge, rng)?;
+                let (state, commitment2) = P2::commit(&pp.1, &statement.1, witness, rng)?;
+                Ok((
+                    Either::Right((state, witness.clone(), simulated_challenge, response1)),
+                    (commitment1, commitment2),
+                ))
+            }
+        }
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        P1::absorb(&commitment.0, transcript)?;
+        P2::absorb(&commitment.1, transcript)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        P1::challenge(transcript)
+    }
+
+    /// The witness was captured by `commit`, so that it always matches the state
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        _witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        match state {
+            Either::Left((state, witness, challenge2, response2)) => {
+                let challenge1 = *challenge - challenge2;
+                let response1 = P1::respond(&pp.0, &statement.0, &witness, state, &challenge1)?;
+                Ok((challenge1, response1, response2))
+            }
+            Either::Right((state, witness, challenge1, response1)) => {
+                let challenge2 = *challenge - challenge1;
+                let response2 = P2::respond(&pp.1, &statement.1, &witness, state, &challenge2)?;
+                Ok((challenge1, response1, response2))
+            }
+        }
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        let (challenge1, response1, response2) = response;
+        let challenge2 = *challenge - challenge1;
+
+        Ok(
+            P1::check(&pp.0, &statement.0, &commitment.0, challenge1, response1)?
+                && P2::check(&pp.1, &statement.1, &commitment.1, &challenge2, response2)?,
+        )
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        let challenge1 = P1::Challenge::rand(rng);
+        let challenge2 = *challenge - challenge1;
+        let (commitment1, response1) = P1::simulate(&pp.0, &statement.0, &challenge1, rng)?;
+        let (commitment2, response2) = P2::simulate(&pp.1, &statement.1, &challenge2, rng)?;
+
+        Ok((
+            (commitment1, commitment2),
+            (challenge1, response1, response2),
+        ))
+    }
+}
@@ -0,0 +1,84 @@
+use ark_crypto_primitives::sponge::{
+    constraints::CryptographicSpongeVar,
+    poseidon::{constraints::PoseidonSpongeVar, PoseidonConfig},
+    Absorb,
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, uint8::UInt8};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{poseidon::PoseidonTranscript, TranscriptProtocol};
+use crate::crypto::commitment::registry::poseidon_config;
+
+pub trait TranscriptGadget<T: TranscriptProtocol, ConstraintF: PrimeField>: Sized {
+    /// Create a new transcript with the given `use_label` flag, as `T::new` does.
+    fn new(cs: ConstraintSystemRef<ConstraintF>, use_label: bool) -> Self;
+
+    /// Append an `item` with the given `label`, as `T::append` does with its value.
+    fn append(
+        &mut self,
+        label: &'static [u8],
+        item: &[UInt8<ConstraintF>],
+    ) -> Result<(), SynthesisError>;
+
+    /// Compute the `label`ed challenge that `T::challenge_scalar` returns in `ConstraintF`.
+    fn challenge_scalar(
+        &mut self,
+        label: &'static [u8],
+    ) -> Result<FpVar<ConstraintF>, SynthesisError>;
+}
+
+/// `PoseidonTranscript` in a circuit over the field of its sponge
+#[derive(Clone)]
+pub struct PoseidonTranscriptVar<F: PrimeField> {
+    sponge: PoseidonSpongeVar<F>,
+    use_label: bool,
+}
+
+impl<F: PrimeField> PoseidonTranscriptVar<F> {
+    pub fn with_config(
+        cs: ConstraintSystemRef<F>,
+        params: &PoseidonConfig<F>,
+        use_label: bool,
+    ) -> Self {
+        Self {
+            sponge: PoseidonSpongeVar::new(cs, params),
+            use_label,
+        }
+    }
+
+    /// Append the field elements `items`, as `PoseidonTranscript::append_scalars` does.
+    pub fn append_scalars(
+        &mut self,
+        label: &'static [u8],
+        items: &[FpVar<F>],
+    ) -> Result<(), SynthesisError> {
+        self.append_label(label)?;
+        self.sponge.absorb(&items)
+    }
+
+    fn append_label(&mut self, label: &'static [u8]) -> Result<(), SynthesisError> {
+        if self.use_label {
+            self.sponge.absorb(&UInt8::constant_vec(label))?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField + Absorb> TranscriptGadget<PoseidonTranscript<F>, F>
+    for PoseidonTranscriptVar<F>
+{
+    fn new(cs: ConstraintSystemRef<F>, use_label: bool) -> Self {
+        Self::with_config(cs, &poseidon_config(), use_label)
+    }
+
+    fn append(&mut self, label: &'static [u8], item: &[UInt8<F>]) -> Result<(), SynthesisError> {
+        self.append_label(label)?;
+        self.sponge.absorb(&item)
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> Result<FpVar<F>, SynthesisError> {
+        self.append_label(label)?;
+        Ok(self.sponge.squeeze_field_elements(1)?.remove(0))
+    }
+}
@@ -0,0 +1,49 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use super::{Encoding, TranscriptProtocol};
+
+/// External data a proof is bound to, e.g. who submits it to which on-chain verifier.
+///
+/// Absorbed before any challenge, it turns a proof into a signature of knowledge on the context:
+/// a proof observed on-chain does not verify for another sender, chain or nonce.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Context {
+    /// Address of the sender, e.g. `msg.sender`
+    pub sender: [u8; 20],
+    /// e.g. `block.chainid`
+    pub chain_id: u64,
+    /// Distinguishes the proofs of one sender, e.g. a counter kept by the verifier
+    pub nonce: u64,
+}
+
+impl Context {
+    pub fn new(sender: [u8; 20], chain_id: u64, nonce: u64) -> Self {
+        Self {
+            sender,
+            chain_id,
+            nonce,
+        }
+    }
+
+    /// `abi.encodePacked(address, uint256, uint256)` with `Encoding::Evm`
+    pub fn to_bytes(&self, encoding: Encoding) -> Vec<u8> {
+        let mut bytes = self.sender.to_vec();
+        match encoding {
+            Encoding::Evm => [self.chain_id, self.nonce].iter().for_each(|n| {
+                bytes.extend([0u8; 24]);
+                bytes.extend(n.to_be_bytes());
+            }),
+            Encoding::Canonical => [self.chain_id, self.nonce]
+                .iter()
+                .for_each(|n| bytes.extend(n.to_le_bytes())),
+        }
+        bytes
+    }
+}
+
+/// Absorb `context`, if any, ahead of the protocol, which must not have drawn a challenge yet
+pub fn bind_context<T: TranscriptProtocol>(context: Option<&Context>, transcript: &mut T) {
+    if let Some(context) = context {
+        transcript.append(b"context", &context.to_bytes(transcript.encoding()));
+    }
+}
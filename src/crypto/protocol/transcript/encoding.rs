@@ -0,0 +1,195 @@
+use ark_ec::{
+    short_weierstrass::{self, SWCurveConfig},
+    twisted_edwards::{self, TECurveConfig},
+    AffineRepr,
+};
+use ark_ff::{Fp, FpConfig, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use ark_std::fmt;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::TranscriptProtocol;
+
+/// Byte layout of the items appended to a transcript
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// Big-endian scalars and points as `x || y`, as the Solidity verifiers lay them out.
+    /// The identity has no affine coordinates and cannot be encoded.
+    #[default]
+    Evm,
+    /// Compressed `CanonicalSerialize` of arkworks, which encodes the identity as well
+    Canonical,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The identity point cannot be encoded with `Encoding::Evm`
+    IdentityPoint,
+    /// An element could not be serialized
+    Serialization,
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IdentityPoint => write!(f, "the identity point has no EVM encoding"),
+            Self::Serialization => write!(f, "failed to serialize an element"),
+        }
+    }
+}
+
+impl ark_std::error::Error for EncodingError {}
+
+impl Encoding {
+    pub fn point<A: AffineRepr>(self, point: &A, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        match self {
+            Self::Evm => {
+                let (x, y) = point.xy().ok_or(EncodingError::IdentityPoint)?;
+                let mut bytes = vec![];
+                y.serialize_uncompressed(&mut bytes)
+                    .map_err(|_| EncodingError::Serialization)?;
+                x.serialize_uncompressed(&mut bytes)
+                    .map_err(|_| EncodingError::Serialization)?;
+                bytes.reverse();
+                dest.extend(bytes);
+            }
+            Self::Canonical => point
+                .serialize_compressed(dest)
+                .map_err(|_| EncodingError::Serialization)?,
+        }
+        Ok(())
+    }
+
+    pub fn scalar<F: PrimeField>(
+        self,
+        scalar: &F,
+        dest: &mut Vec<u8>,
+    ) -> Result<(), EncodingError> {
+        match self {
+            Self::Evm => {
+                let mut bytes = vec![];
+                scalar
+                    .serialize_uncompressed(&mut bytes)
+                    .map_err(|_| EncodingError::Serialization)?;
+                bytes.reverse();
+                dest.extend(bytes);
+            }
+            Self::Canonical => scalar
+                .serialize_compressed(dest)
+                .map_err(|_| EncodingError::Serialization)?,
+        }
+        Ok(())
+    }
+
+    pub fn points<A: AffineRepr>(
+        self,
+        points: &[A],
+        dest: &mut Vec<u8>,
+    ) -> Result<(), EncodingError> {
+        let bytes = cfg_iter!(points)
+            .map(|p| {
+                let mut bytes = vec![];
+                self.point(p, &mut bytes)?;
+                Ok(bytes)
+            })
+            .collect::<Result<Vec<_>, EncodingError>>()?;
+        dest.extend(bytes.concat());
+        Ok(())
+    }
+
+    pub fn scalars<F: PrimeField>(
+        self,
+        scalars: &[F],
+        dest: &mut Vec<u8>,
+    ) -> Result<(), EncodingError> {
+        scalars.iter().try_for_each(|s| self.scalar(s, dest))
+    }
+}
+
+/// Items with a fixed byte layout in each `Encoding`, so that every protocol appends
+/// points and scalars the same way.
+///
+/// Points and scalars of a generic curve cannot implement the trait next to each other,
+/// and go through `Encoding::point` and `Encoding::scalar`, or `Points` and `Scalars`.
+pub trait AppendToTranscript {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError>;
+
+    /// Append `self` under the encoding of `transcript`
+    fn append_to_transcript<T: TranscriptProtocol>(
+        &self,
+        label: &'static [u8],
+        transcript: &mut T,
+    ) -> Result<(), EncodingError> {
+        let mut bytes = vec![];
+        self.encode(transcript.encoding(), &mut bytes)?;
+        transcript.append(label, &bytes);
+        Ok(())
+    }
+}
+
+/// Points of any curve, appended one after the other
+pub struct Points<'a, A>(pub &'a [A]);
+
+/// Scalars of any field, appended one after the other
+pub struct Scalars<'a, F>(pub &'a [F]);
+
+impl<'a, A: AffineRepr> AppendToTranscript for Points<'a, A> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(self.0, dest)
+    }
+}
+
+impl<'a, F: PrimeField> AppendToTranscript for Scalars<'a, F> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.scalars(self.0, dest)
+    }
+}
+
+impl<P: SWCurveConfig> AppendToTranscript for short_weierstrass::Affine<P> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.point(self, dest)
+    }
+}
+
+impl<P: TECurveConfig> AppendToTranscript for twisted_edwards::Affine<P> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.point(self, dest)
+    }
+}
+
+impl<P: FpConfig<N>, const N: usize> AppendToTranscript for Fp<P, N> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.scalar(self, dest)
+    }
+}
+
+impl AppendToTranscript for u64 {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        match encoding {
+            Encoding::Evm => dest.extend(self.to_be_bytes()),
+            Encoding::Canonical => dest.extend(self.to_le_bytes()),
+        }
+        Ok(())
+    }
+}
+
+impl<T: AppendToTranscript> AppendToTranscript for [T] {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        self.iter().try_for_each(|item| item.encode(encoding, dest))
+    }
+}
+
+impl<T: AppendToTranscript> AppendToTranscript for Vec<T> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        self.as_slice().encode(encoding, dest)
+    }
+}
+
+impl<A: AppendToTranscript, B: AppendToTranscript> AppendToTranscript for (A, B) {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        self.0.encode(encoding, dest)?;
+        self.1.encode(encoding, dest)
+    }
+}
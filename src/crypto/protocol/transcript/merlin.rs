@@ -0,0 +1,67 @@
+use ark_ff::PrimeField;
+
+use super::{strobe::Strobe128, Encoding, TranscriptProtocol};
+
+const MERLIN_PROTOCOL_LABEL: &[u8] = b"Merlin v1.0";
+
+/// Merlin transcript over STROBE-128.
+/// Labels are always absorbed, and every message and challenge is framed with its length,
+/// so the transcript does not depend on the `use_label` flag.
+#[derive(Clone)]
+pub struct MerlinTranscript {
+    strobe: Strobe128,
+    encoding: Encoding,
+}
+
+impl MerlinTranscript {
+    /// A transcript for the protocol named `label`
+    pub fn with_label(label: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            strobe: Strobe128::new(MERLIN_PROTOCOL_LABEL),
+            encoding: Encoding::default(),
+        };
+        transcript.append(b"dom-sep", label);
+        transcript
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Fill `dest` with challenge bytes bound to everything absorbed so far
+    pub fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&Self::encode_length(dest.len()), true);
+        self.strobe.prf(dest, false);
+    }
+
+    fn encode_length(len: usize) -> [u8; 4] {
+        u32::try_from(len)
+            .expect("Merlin messages are shorter than 4 GiB")
+            .to_le_bytes()
+    }
+}
+
+impl TranscriptProtocol for MerlinTranscript {
+    fn new(_use_label: bool) -> Self {
+        Self::with_label(b"cc-snark")
+    }
+
+    fn append(&mut self, label: &'static [u8], item: &[u8]) {
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&Self::encode_length(item.len()), true);
+        self.strobe.ad(item, false);
+    }
+
+    /// 64 bytes are reduced, so that the challenge is close to uniform
+    fn challenge_scalar<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        let mut bytes = [0u8; 64];
+        self.challenge_bytes(label, &mut bytes);
+        F::from_le_bytes_mod_order(&bytes)
+    }
+
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+}
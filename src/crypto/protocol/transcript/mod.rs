@@ -1,6 +1,15 @@
 use ark_ff::PrimeField;
 
+pub mod constraints;
+pub mod context;
+pub mod encoding;
+pub mod merlin;
+pub mod poseidon;
 pub mod sha3;
+mod strobe;
+
+pub use context::{bind_context, Context};
+pub use encoding::{AppendToTranscript, Encoding, EncodingError, Points, Scalars};
 
 pub trait TranscriptProtocol: Clone {
     /// Create a new transcript with the given `use_label` flag.
@@ -11,4 +20,9 @@ pub trait TranscriptProtocol: Clone {
 
     /// Compute a `label`ed challenge variable.
     fn challenge_scalar<F: PrimeField>(&mut self, label: &'static [u8]) -> F;
+
+    /// The byte layout of the items appended through `AppendToTranscript`.
+    fn encoding(&self) -> Encoding {
+        Encoding::Evm
+    }
 }
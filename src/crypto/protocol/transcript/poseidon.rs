@@ -0,0 +1,73 @@
+use super::{Encoding, TranscriptProtocol};
+use crate::crypto::commitment::registry::poseidon_config;
+use ark_crypto_primitives::sponge::{
+    poseidon::{PoseidonConfig, PoseidonSponge},
+    Absorb, CryptographicSponge, FieldBasedCryptographicSponge,
+};
+use ark_ff::{BigInteger, PrimeField};
+
+/// Transcript over the Poseidon sponge of `F`, replayed in circuits over `F` by
+/// `PoseidonTranscriptVar`.
+///
+/// Byte items are packed into elements of `F` behind their length, while `append_scalars`
+/// absorbs elements of `F` as they are, which is what keeps the gadget cheap.
+#[derive(Clone)]
+pub struct PoseidonTranscript<F: PrimeField + Absorb> {
+    sponge: PoseidonSponge<F>,
+    use_label: bool,
+    encoding: Encoding,
+}
+
+impl<F: PrimeField + Absorb> PoseidonTranscript<F> {
+    pub fn with_config(params: &PoseidonConfig<F>, use_label: bool) -> Self {
+        Self {
+            sponge: PoseidonSponge::new(params),
+            use_label,
+            encoding: Encoding::default(),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Append the field elements `items` without going through their bytes.
+    pub fn append_scalars(&mut self, label: &'static [u8], items: &[F]) {
+        self.append_label(label);
+        self.sponge.absorb(&items);
+    }
+
+    /// Compute a `label`ed challenge in the native field of the sponge.
+    pub fn challenge_native(&mut self, label: &'static [u8]) -> F {
+        self.append_label(label);
+        self.sponge.squeeze_native_field_elements(1)[0]
+    }
+
+    fn append_label(&mut self, label: &'static [u8]) {
+        if self.use_label {
+            self.sponge.absorb(&label);
+        }
+    }
+}
+
+impl<F: PrimeField + Absorb> TranscriptProtocol for PoseidonTranscript<F> {
+    fn new(use_label: bool) -> Self {
+        Self::with_config(&poseidon_config(), use_label)
+    }
+
+    fn append(&mut self, label: &'static [u8], item: &[u8]) {
+        self.append_label(label);
+        self.sponge.absorb(&item);
+    }
+
+    /// The challenge is that of `challenge_native`, reduced into `G` when the fields differ
+    fn challenge_scalar<G: PrimeField>(&mut self, label: &'static [u8]) -> G {
+        let challenge = self.challenge_native(label);
+        G::from_le_bytes_mod_order(&challenge.into_bigint().to_bytes_le())
+    }
+
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+}
@@ -1,4 +1,4 @@
-use super::TranscriptProtocol;
+use super::{Encoding, TranscriptProtocol};
 use ark_ff::{BigInteger, PrimeField};
 use sha3::{Digest, Keccak256};
 
@@ -6,6 +6,14 @@ use sha3::{Digest, Keccak256};
 pub struct SHA3Base {
     bytes: Vec<u8>,
     use_label: bool,
+    encoding: Encoding,
+}
+
+impl SHA3Base {
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
 }
 
 impl TranscriptProtocol for SHA3Base {
@@ -13,6 +21,7 @@ impl TranscriptProtocol for SHA3Base {
         SHA3Base {
             bytes: Vec::new(),
             use_label,
+            encoding: Encoding::default(),
         }
     }
 
@@ -35,4 +44,8 @@ impl TranscriptProtocol for SHA3Base {
         self.bytes.extend(challenge.into_bigint().to_bytes_be());
         challenge
     }
+
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
 }
@@ -0,0 +1,113 @@
+//! STROBE-128 over Keccak-f[1600], restricted to the operations a Merlin transcript needs.
+
+const STROBE_R: u8 = 166;
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_M: u8 = 1 << 4;
+const FLAG_K: u8 = 1 << 5;
+
+#[derive(Clone)]
+pub(super) struct Strobe128 {
+    state: [u8; 200],
+    pos: u8,
+    pos_begin: u8,
+    cur_flags: u8,
+}
+
+impl Strobe128 {
+    pub fn new(protocol_label: &[u8]) -> Self {
+        let mut state = [0u8; 200];
+        state[0..6].copy_from_slice(&[1, STROBE_R + 2, 1, 0, 1, 96]);
+        state[6..18].copy_from_slice(b"STROBEv1.0.2");
+        permute(&mut state);
+
+        let mut strobe = Self {
+            state,
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+        };
+        strobe.meta_ad(protocol_label, false);
+        strobe
+    }
+
+    /// Absorb framing data, e.g. a label or a length
+    pub fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A, more);
+        self.absorb(data);
+    }
+
+    /// Absorb associated data
+    pub fn ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A, more);
+        self.absorb(data);
+    }
+
+    /// Squeeze pseudo-random bytes into `data`
+    pub fn prf(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(data);
+    }
+
+    fn run_f(&mut self) {
+        self.state[self.pos as usize] ^= self.pos_begin;
+        self.state[self.pos as usize + 1] ^= 0x04;
+        self.state[STROBE_R as usize + 1] ^= 0x80;
+        permute(&mut self.state);
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.state[self.pos as usize] ^= byte;
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn squeeze(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.state[self.pos as usize];
+            self.state[self.pos as usize] = 0;
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn begin_op(&mut self, flags: u8, more: bool) {
+        // continue the previous operation, which must be the same one
+        if more {
+            debug_assert_eq!(self.cur_flags, flags);
+            return;
+        }
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos + 1;
+        self.cur_flags = flags;
+        self.absorb(&[old_begin, flags]);
+
+        // cipher operations start on a fresh block
+        if flags & (FLAG_C | FLAG_K) != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+}
+
+/// Keccak-f[1600] over the state as 25 little-endian lanes
+fn permute(state: &mut [u8; 200]) {
+    let mut lanes = [0u64; 25];
+    for (lane, bytes) in lanes.iter_mut().zip(state.chunks_exact(8)) {
+        *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    keccak::f1600(&mut lanes);
+    for (lane, bytes) in lanes.iter().zip(state.chunks_exact_mut(8)) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+}
@@ -0,0 +1,170 @@
+use ark_crypto_primitives::snark::BooleanInputVar;
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::Field;
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
+    eq::EqGadget,
+    groups::CurveVar,
+    pairing::PairingVar,
+    ToBitsGadget,
+};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+use ark_std::{borrow::Borrow, marker::PhantomData, vec::Vec};
+
+use super::{PreparedVerifyingKey, Proof};
+
+/// Field of the circuits verifying proofs over `E`, e.g. the scalar field of BW6-761 for BLS12-377
+pub type BasePrimeField<E> =
+    <<<E as Pairing>::G1 as CurveGroup>::BaseField as Field>::BasePrimeField;
+
+/// Public inputs of the verified proof, as the little-endian bits of its scalars
+pub type InputVar<E> = BooleanInputVar<<E as Pairing>::ScalarField, BasePrimeField<E>>;
+
+/// A cc-Groth16 proof in a circuit over `BasePrimeField<E>`
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P::G1Var: Clone, P::G2Var: Clone"))]
+pub struct ProofVar<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> {
+    pub a: P::G1Var,
+    pub b: P::G2Var,
+    pub c: P::G1Var,
+    /// The proof dependent commitment, with whatever the verifier adds to it, e.g. an aggregation
+    pub d: P::G1Var,
+}
+
+/// A prepared verifying key in a circuit over `BasePrimeField<E>`
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P::G1Var: Clone, P::GTVar: Clone, P::G2PreparedVar: Clone"))]
+pub struct PreparedVerifyingKeyVar<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> {
+    pub alpha_g1_beta_g2: P::GTVar,
+    pub gamma_g2_neg_pc: P::G2PreparedVar,
+    pub delta_g2_neg_pc: P::G2PreparedVar,
+    pub gamma_abc_g1: Vec<P::G1Var>,
+}
+
+/// Verifier of `CCGroth16` over `E` in a circuit over `BasePrimeField<E>`,
+/// with `P` the pairing gadget of `E`, e.g. on the 2-chain BLS12-377 / BW6-761
+pub struct CCGroth16VerifierGadget<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> {
+    _pairing: PhantomData<(E, P)>,
+}
+
+impl<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> CCGroth16VerifierGadget<E, P> {
+    /// `gamma_abc_g1[0] + Σ public_inputs[i] gamma_abc_g1[i + 1]`, as `CCGroth16::prepare_inputs`
+    pub fn prepare_inputs(
+        pvk: &PreparedVerifyingKeyVar<E, P>,
+        public_inputs: &InputVar<E>,
+    ) -> Result<P::G1Var, SynthesisError> {
+        let public_inputs = public_inputs.clone().into_iter().collect::<Vec<_>>();
+        if public_inputs.len() + 1 != pvk.gamma_abc_g1.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+
+        let mut g_ic = pvk.gamma_abc_g1[0].clone();
+        for (input, b) in public_inputs.iter().zip(pvk.gamma_abc_g1.iter().skip(1)) {
+            g_ic += b.scalar_mul_le(input.to_bits_le()?.iter())?;
+        }
+
+        Ok(g_ic)
+    }
+
+    /// `e(A, B) = e(alpha, beta) e(D + prepared_inputs, gamma) e(C, delta)`,
+    /// as `CCGroth16::verify_proof_with_prepared_inputs`
+    pub fn verify_proof_with_prepared_inputs(
+        pvk: &PreparedVerifyingKeyVar<E, P>,
+        proof: &ProofVar<E, P>,
+        prepared_inputs: &P::G1Var,
+    ) -> Result<Boolean<BasePrimeField<E>>, SynthesisError> {
+        let qap = P::miller_loop(
+            &[
+                P::prepare_g1(&proof.a)?,
+                P::prepare_g1(&(proof.d.clone() + prepared_inputs))?,
+                P::prepare_g1(&proof.c)?,
+            ],
+            &[
+                P::prepare_g2(&proof.b)?,
+                pvk.gamma_g2_neg_pc.clone(),
+                pvk.delta_g2_neg_pc.clone(),
+            ],
+        )?;
+        let test = P::final_exponentiation(&qap)?;
+
+        test.is_eq(&pvk.alpha_g1_beta_g2)
+    }
+
+    /// Verify `proof` with respect to `public_inputs`, as `CCGroth16::verify_proof`
+    pub fn verify(
+        pvk: &PreparedVerifyingKeyVar<E, P>,
+        proof: &ProofVar<E, P>,
+        public_inputs: &InputVar<E>,
+    ) -> Result<Boolean<BasePrimeField<E>>, SynthesisError> {
+        let prepared_inputs = Self::prepare_inputs(pvk, public_inputs)?;
+        Self::verify_proof_with_prepared_inputs(pvk, proof, &prepared_inputs)
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> AllocVar<Proof<E>, BasePrimeField<E>>
+    for ProofVar<E, P>
+{
+    fn new_variable<T: Borrow<Proof<E>>>(
+        cs: impl Into<Namespace<BasePrimeField<E>>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        f().and_then(|proof| {
+            let Proof { a, b, c, d } = proof.borrow().clone();
+            let a = P::G1Var::new_variable(ark_relations::ns!(cs, "a"), || Ok(a), mode)?;
+            let b = P::G2Var::new_variable(ark_relations::ns!(cs, "b"), || Ok(b), mode)?;
+            let c = P::G1Var::new_variable(ark_relations::ns!(cs, "c"), || Ok(c), mode)?;
+            let d = P::G1Var::new_variable(ark_relations::ns!(cs, "d"), || Ok(d), mode)?;
+
+            Ok(Self { a, b, c, d })
+        })
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, BasePrimeField<E>>>
+    AllocVar<PreparedVerifyingKey<E>, BasePrimeField<E>> for PreparedVerifyingKeyVar<E, P>
+{
+    fn new_variable<T: Borrow<PreparedVerifyingKey<E>>>(
+        cs: impl Into<Namespace<BasePrimeField<E>>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        f().and_then(|pvk| {
+            let pvk = pvk.borrow();
+            let alpha_g1_beta_g2 = P::GTVar::new_variable(
+                ark_relations::ns!(cs, "alpha_g1_beta_g2"),
+                || Ok(pvk.alpha_g1_beta_g2),
+                mode,
+            )?;
+            let gamma_g2_neg_pc = P::G2PreparedVar::new_variable(
+                ark_relations::ns!(cs, "gamma_g2_neg_pc"),
+                || Ok(pvk.gamma_g2_neg_pc.clone()),
+                mode,
+            )?;
+            let delta_g2_neg_pc = P::G2PreparedVar::new_variable(
+                ark_relations::ns!(cs, "delta_g2_neg_pc"),
+                || Ok(pvk.delta_g2_neg_pc.clone()),
+                mode,
+            )?;
+            let gamma_abc_g1 = Vec::new_variable(
+                ark_relations::ns!(cs, "gamma_abc_g1"),
+                || Ok(pvk.vk.gamma_abc_g1.clone()),
+                mode,
+            )?;
+
+            Ok(Self {
+                alpha_g1_beta_g2,
+                gamma_g2_neg_pc,
+                delta_g2_neg_pc,
+                gamma_abc_g1,
+            })
+        })
+    }
+}
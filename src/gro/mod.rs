@@ -2,6 +2,9 @@ use crate::snark::{CircuitSpecificSetupCCSNARK, CCSNARK};
 /// Reduce an R1CS instance to a *Quadratic Arithmetic Program* instance.
 pub use ark_groth16::r1cs_to_qap;
 
+/// Verify proofs in circuits over the base field, e.g. for recursion on a 2-chain of curves.
+pub mod constraints;
+
 /// Data structures used by the prover, verifier, and generator.
 pub mod data_structures;
 
@@ -0,0 +1,217 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    gro,
+    linker::{am_com_eq, am_eq, comp_am_com_eq, dual_eq, Error},
+    solidity::Solidity,
+};
+
+use super::LinkError;
+
+/// Proof of a CCGroth16 circuit, with the linker proof tying its committed witness
+/// to commitments made outside of the SNARK
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LinkedProof<E: Pairing, L: CanonicalSerialize + CanonicalDeserialize> {
+    pub snark: gro::Proof<E>,
+    pub link: L,
+}
+
+/// Linked to a batch of commitments under `batch_g1`, see `CpLink::prove_batch`
+pub type BatchLinkedProof<E> = LinkedProof<E, am_eq::Proof<<E as Pairing>::G1>>;
+
+/// Linked to commitments under an external key, see `CpLink::prove_external`
+pub type ExternalLinkedProof<E> = LinkedProof<E, comp_am_com_eq::Proof<<E as Pairing>::G1>>;
+
+impl<E: Pairing, L: CanonicalSerialize + CanonicalDeserialize + Solidity> Solidity
+    for LinkedProof<E, L>
+where
+    E::G1Affine: Solidity,
+    E::G2Affine: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [self.snark.to_solidity(), self.link.to_solidity()].concat()
+    }
+}
+
+/// What the prover commits to ahead of the SNARK: the proof dependent commitment,
+/// and the statement of the linker with its witness
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkState<E: Pairing, I, W> {
+    pub commitment: gro::Commitment<E>,
+    pub instance: I,
+    pub witness: W,
+}
+
+/// See `CpLink::commit_batch`
+pub type BatchState<E> =
+    LinkState<E, am_eq::Instance<<E as Pairing>::G1>, am_eq::Witness<<E as Pairing>::G1>>;
+
+/// See `CpLink::commit_external`
+pub type ExternalState<E> =
+    LinkState<E, am_com_eq::Instance<<E as Pairing>::G1>, am_com_eq::Witness<<E as Pairing>::G1>>;
+
+/// Proofs of two circuits over the same committed witness, see `CpLink::prove_dual`
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DualLinkedProof<E: Pairing> {
+    pub snark1: gro::Proof<E>,
+    pub snark2: gro::Proof<E>,
+    pub link: dual_eq::Proof<E::G1>,
+}
+
+impl<E: Pairing> Solidity for DualLinkedProof<E>
+where
+    E::G1Affine: Solidity,
+    E::G2Affine: Solidity,
+    E::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [
+            self.snark1.to_solidity(),
+            self.snark2.to_solidity(),
+            self.link.to_solidity(),
+        ]
+        .concat()
+    }
+}
+
+/// The proof dependent commitments of both circuits to the same committed witness,
+/// see `CpLink::commit_dual`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DualState<E: Pairing> {
+    pub commitment1: gro::Commitment<E>,
+    pub commitment2: gro::Commitment<E>,
+    pub instance: dual_eq::Instance<E::G1>,
+    pub witness: dual_eq::Witness<E::G1>,
+}
+
+/// Commitments proved so far in an incremental batch, where the next segment starts.
+///
+/// Each segment draws a challenge of its own, chained to the one of the previous segment,
+/// and aggregates its commitments from `tau^(len + 1)` on, as the `initial` of `aggregate`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Epoch<F: Field> {
+    /// Challenge of the last segment, `None` before the first one
+    pub tau: Option<F>,
+    /// Number of commitments in the previous segments
+    pub len: usize,
+}
+
+impl<F: Field> Epoch<F> {
+    /// The power of `tau` weighting the first commitment of the next segment
+    pub fn initial(&self, tau: F) -> F {
+        tau.pow([self.len as u64 + 1])
+    }
+
+    /// The epoch after a segment of `len` commitments proved with the challenge `tau`
+    pub fn extend(&self, tau: F, len: usize) -> Self {
+        Self {
+            tau: Some(tau),
+            len: self.len + len,
+        }
+    }
+}
+
+/// Proof of `len` consecutive commitments of an incremental batch: the SNARK over the segment,
+/// and `c`, the aggregate of the segment from its own `tau` that the SNARK is linked to
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Segment<E: Pairing> {
+    pub len: usize,
+    pub snark: gro::Proof<E>,
+    pub c: E::G1Affine,
+}
+
+/// Batch proof grown one segment at a time, see `CpLink::prove_segment`.
+/// The linker proof is the linking step of all the segments: it opens their aggregates,
+/// combined with the powers of a last challenge `rho`, see `CpLink::prove_incremental`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IncrementalProof<E: Pairing> {
+    pub segments: Vec<Segment<E>>,
+    pub link: am_eq::Proof<E::G1>,
+}
+
+impl<E: Pairing> IncrementalProof<E> {
+    /// Number of commitments proved by all the segments
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+/// The segments proved from `start` to `epoch`, with the openings of their aggregates.
+/// Extending the batch proves the new segments only, the previous ones are kept as is,
+/// and the states of consecutive ranges of the batch are joined with `append`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncrementalState<E: Pairing> {
+    pub start: Epoch<E::ScalarField>,
+    pub epoch: Epoch<E::ScalarField>,
+    pub segments: Vec<Segment<E>>,
+    pub witnesses: Vec<am_eq::Witness<E::G1>>,
+}
+
+impl<E: Pairing> IncrementalState<E> {
+    /// No segment yet, the next one starting at `epoch`
+    pub fn new(epoch: Epoch<E::ScalarField>) -> Self {
+        Self {
+            start: epoch,
+            epoch,
+            segments: vec![],
+            witnesses: vec![],
+        }
+    }
+
+    /// Append the proof of the next segment, proved with the challenge `tau`,
+    /// and the opening of its aggregate
+    pub fn push(
+        &mut self,
+        segment: Segment<E>,
+        witness: am_eq::Witness<E::G1>,
+        tau: E::ScalarField,
+    ) {
+        self.epoch = self.epoch.extend(tau, segment.len);
+        self.segments.push(segment);
+        self.witnesses.push(witness);
+    }
+
+    /// Append the segments of `other`, which must start where these end
+    pub fn append(&mut self, other: Self) -> Result<(), LinkError> {
+        if other.start != self.epoch {
+            return Err(Error::MalformedProof.into());
+        }
+        self.epoch = other.epoch;
+        self.segments.extend(other.segments);
+        self.witnesses.extend(other.witnesses);
+
+        Ok(())
+    }
+}
+
+/// Batch proved in chunks of the same length against one key, see `CpLink::commit_chunked`.
+/// Each chunk is a SNARK over its slice of `c_hat`, whose aggregate the verifier computes,
+/// and the linker proof is the final aggregation step, shared by all the chunks.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ChunkedProof<E: Pairing> {
+    pub chunks: Vec<gro::Proof<E>>,
+    pub link: am_eq::Proof<E::G1>,
+}
+
+/// The proof dependent commitment of each chunk, with the linker statement
+/// over the whole batch, see `CpLink::commit_chunked`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChunkedState<E: Pairing> {
+    pub chunk_len: usize,
+    pub commitments: Vec<gro::Commitment<E>>,
+    pub instance: am_eq::Instance<E::G1>,
+    pub witness: am_eq::Witness<E::G1>,
+}
+
+impl<E: Pairing> ChunkedState<E> {
+    /// The power of `tau` weighting the first commitment of the chunk `index`
+    pub fn initial(&self, index: usize) -> E::ScalarField {
+        self.instance.tau.pow([(index * self.chunk_len) as u64 + 1])
+    }
+}
@@ -0,0 +1,70 @@
+use ark_relations::r1cs::SynthesisError;
+use ark_std::fmt;
+
+use crate::{
+    crypto::commitment::{BatchCommitmentError, RegistryError},
+    linker,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkError {
+    /// The SNARK could not be proved or verified, e.g. on malformed public inputs
+    Snark(SynthesisError),
+    /// The linker proof could not be created or verified
+    Linker(linker::Error),
+    /// The batch of commitments is malformed
+    Commitment(BatchCommitmentError),
+    /// The registry of commitments could not be built
+    Registry(RegistryError),
+    /// The committing key does not have the generators the link needs
+    MalformedKey,
+    /// The SNARK proof does not verify
+    InvalidSnark,
+    /// The SNARK proof verifies, but is not linked to the commitments
+    InvalidLink,
+    /// The message given for the commitment at `index` does not open it
+    InvalidOpening { index: usize },
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Snark(err) => write!(f, "snark failure: {}", err),
+            Self::Linker(err) => write!(f, "linker failure: {}", err),
+            Self::Commitment(err) => write!(f, "commitment failure: {}", err),
+            Self::Registry(err) => write!(f, "registry failure: {}", err),
+            Self::MalformedKey => write!(f, "malformed committing key"),
+            Self::InvalidSnark => write!(f, "invalid snark proof"),
+            Self::InvalidLink => write!(f, "invalid link proof"),
+            Self::InvalidOpening { index } => {
+                write!(f, "message does not open the commitment at index {}", index)
+            }
+        }
+    }
+}
+
+impl ark_std::error::Error for LinkError {}
+
+impl From<SynthesisError> for LinkError {
+    fn from(err: SynthesisError) -> Self {
+        Self::Snark(err)
+    }
+}
+
+impl From<linker::Error> for LinkError {
+    fn from(err: linker::Error) -> Self {
+        Self::Linker(err)
+    }
+}
+
+impl From<BatchCommitmentError> for LinkError {
+    fn from(err: BatchCommitmentError) -> Self {
+        Self::Commitment(err)
+    }
+}
+
+impl From<RegistryError> for LinkError {
+    fn from(err: RegistryError) -> Self {
+        Self::Registry(err)
+    }
+}
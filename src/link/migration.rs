@@ -0,0 +1,189 @@
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::PrimeField;
+use ark_groth16::r1cs_to_qap::R1CSToQAP;
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    Zero,
+};
+
+use crate::{
+    crypto::{
+        commitment::{pedersen::Pedersen, registry::Registry, BatchCommitmentScheme},
+        protocol::{
+            sigma::SigmaProtocol,
+            transcript::{Context, TranscriptProtocol},
+        },
+    },
+    linker::{
+        dual_eq::{self, DualEq, PublicParameters},
+        Error,
+    },
+};
+
+use super::{CpLink, LinkError};
+
+/// Proof that a commitment under the `batch_g1` of an old key and one under the `batch_g1`
+/// of a new key open to the same message, see `CpLink::retarget`
+pub type RetargetProof<E> = dual_eq::Proof<<E as Pairing>::G1>;
+
+/// The commitments to a message under both keys, with the message as the witness
+type RetargetStatement<C> = (dual_eq::Instance<C>, dual_eq::Witness<C>);
+
+/// A registry re-targeted to a new key, with the proof of each of its commitments
+pub struct Migration<E: Pairing>
+where
+    <E::G1 as CurveGroup>::BaseField: PrimeField + Absorb,
+{
+    pub registry: Registry<E::G1>,
+    pub proofs: Vec<RetargetProof<E>>,
+}
+
+impl<E: Pairing, QAP: R1CSToQAP> CpLink<E, QAP> {
+    /// Commit to `message` under both keys of `pp`, see `retarget_parameters`,
+    /// and prove that the two commitments open to it.
+    /// Returns the old commitment `d1` and the new one `d2`, with the proof
+    pub fn retarget<T, R>(
+        pp: &PublicParameters<E::G1>,
+        message: &[E::ScalarField],
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<(dual_eq::Instance<E::G1>, RetargetProof<E>), LinkError>
+    where
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let retarget_timer = start_timer!(|| "CpLink::Retarget");
+        let (instance, witness) = Self::retarget_statement(pp, message)?;
+        let proof = DualEq::prove_with_context(pp, &instance, &witness, context, transcript, rng)?;
+        end_timer!(retarget_timer);
+
+        Ok((instance, proof))
+    }
+
+    /// The commitments to `message` under both keys, padded with zeros as in a ragged batch
+    fn retarget_statement(
+        pp: &PublicParameters<E::G1>,
+        message: &[E::ScalarField],
+    ) -> Result<RetargetStatement<E::G1>, LinkError> {
+        let d1 = Pedersen::<E::G1>::batch_commit(&pp.ck1.g, &[message])?[0];
+        let d2 = Pedersen::<E::G1>::batch_commit(&pp.ck2.g, &[message])?[0];
+
+        let mut w = message.to_vec();
+        w.resize(pp.ck1.g.len(), E::ScalarField::zero());
+
+        Ok((
+            dual_eq::Instance { d1, d2 },
+            dual_eq::Witness {
+                w,
+                alpha1: vec![],
+                alpha2: vec![],
+            },
+        ))
+    }
+
+    /// Verify that the new commitment `d2` of `instance` opens to the same message
+    /// as the old one `d1`, under the context the proof was made with
+    pub fn verify_retarget<T: TranscriptProtocol>(
+        pp: &PublicParameters<E::G1>,
+        instance: &dual_eq::Instance<E::G1>,
+        proof: &RetargetProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        if !DualEq::verify_with_context(pp, instance, proof, context, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+
+        Ok(())
+    }
+
+    /// Re-target every commitment of `registry` to the new key of `pp`, given the message
+    /// each one opens to, into a registry of the same capacity and order.
+    /// Each proof is made on a copy of `transcript`, as `verify_migration` checks it.
+    pub fn migrate<T, R>(
+        pp: &PublicParameters<E::G1>,
+        registry: &Registry<E::G1>,
+        messages: &[Vec<E::ScalarField>],
+        context: Option<&Context>,
+        transcript: &T,
+        rng: &mut R,
+    ) -> Result<Migration<E>, LinkError>
+    where
+        <E::G1 as CurveGroup>::BaseField: PrimeField + Absorb,
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let migrate_timer = start_timer!(|| "CpLink::Migrate");
+        if messages.len() != registry.len() {
+            return Err(Error::LengthMismatch {
+                expected: registry.len(),
+                found: messages.len(),
+            }
+            .into());
+        }
+
+        let depth = registry.capacity().trailing_zeros() as usize;
+        let mut migrated = Registry::new(registry.params.clone(), depth)?;
+        let mut proofs = Vec::with_capacity(messages.len());
+        for (index, message) in messages.iter().enumerate() {
+            let (instance, witness) = Self::retarget_statement(pp, message)?;
+            if registry.get(index) != Some(&instance.d1) {
+                return Err(LinkError::InvalidOpening { index });
+            }
+
+            let mut transcript = transcript.clone();
+            proofs.push(DualEq::prove_with_context(
+                pp,
+                &instance,
+                &witness,
+                context,
+                &mut transcript,
+                rng,
+            )?);
+            migrated.insert(&instance.d2)?;
+        }
+        end_timer!(migrate_timer);
+
+        Ok(Migration {
+            registry: migrated,
+            proofs,
+        })
+    }
+
+    /// Verify that the commitment at each index of `new` is re-targeted from the one
+    /// at the same index of `old`, reporting the index of the first invalid proof
+    pub fn verify_migration<T: TranscriptProtocol>(
+        pp: &PublicParameters<E::G1>,
+        old: &Registry<E::G1>,
+        new: &Registry<E::G1>,
+        proofs: &[RetargetProof<E>],
+        context: Option<&Context>,
+        transcript: &T,
+    ) -> Result<(), LinkError>
+    where
+        <E::G1 as CurveGroup>::BaseField: PrimeField + Absorb,
+    {
+        let verifier_timer = start_timer!(|| "CpLink::Verify Migration");
+        for (expected, found) in [(old.len(), new.len()), (old.len(), proofs.len())] {
+            if expected != found {
+                return Err(Error::LengthMismatch { expected, found }.into());
+            }
+        }
+
+        for (index, proof) in proofs.iter().enumerate() {
+            let instance = match (old.get(index), new.get(index)) {
+                (Some(d1), Some(d2)) => dual_eq::Instance { d1: *d1, d2: *d2 },
+                _ => return Err(Error::InvalidProof(index).into()),
+            };
+            let mut transcript = transcript.clone();
+            if !DualEq::verify_with_context(pp, &instance, proof, context, &mut transcript)? {
+                return Err(Error::InvalidProof(index).into());
+            }
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! CP-link between CCGroth16 proofs and commitments made outside of the circuit.
+//!
+//! The committed witness of the SNARK is bound by the proof dependent commitment `D`,
+//! and a linker proof shows that `D` opens to the same values as the external commitments:
+//! - `AmEq` for a batch committed under `batch_g1`, aggregated with the challenge `tau`
+//!   that the circuit takes as its first public input,
+//! - `CompAmComEq` for commitments under a key of their own,
+//! - `DualEq` for the proof dependent commitment of another circuit.
+//!
+//! A batch may also grow one segment at a time, each segment proved on its own and all of them
+//! linked at once, see `IncrementalProof`, or be split in chunks proved against a key sized
+//! to one chunk, see `ChunkedProof`.
+//!
+//! Commitments under the `batch_g1` of a key are re-targeted to the key of a new setup
+//! with `DualEq`, one at a time or for a whole registry, see `Migration`.
+
+mod data_structure;
+pub use data_structure::*;
+
+mod errors;
+pub use errors::LinkError;
+
+mod migration;
+pub use migration::{Migration, RetargetProof};
+
+mod prover;
+mod verifier;
+
+use ark_ec::pairing::Pairing;
+use ark_groth16::r1cs_to_qap::{LibsnarkReduction, R1CSToQAP};
+use ark_std::marker::PhantomData;
+
+use crate::{
+    gro,
+    linker::{am_com_eq::CommittingKey, am_eq::PublicParameters, dual_eq},
+};
+
+pub struct CpLink<E: Pairing, QAP: R1CSToQAP = LibsnarkReduction> {
+    _p: PhantomData<(E, QAP)>,
+}
+
+impl<E: Pairing, QAP: R1CSToQAP> CpLink<E, QAP> {
+    /// `batch_g1` split into the message generators and the last one, which blinds,
+    /// on both sides of `AmEq`
+    pub fn batch_parameters(
+        ck: &gro::CommittingKey<E>,
+    ) -> Result<PublicParameters<E::G1>, LinkError> {
+        let n = ck.batch_g1.len();
+        if n < 2 {
+            return Err(LinkError::MalformedKey);
+        }
+        let lck = CommittingKey {
+            g: ck.batch_g1[..n - 1].to_vec(),
+            h: ck.batch_g1[n - 1..].to_vec(),
+        };
+
+        Ok(PublicParameters {
+            poly_ck: lck.clone(),
+            coeff_ck: lck,
+        })
+    }
+
+    /// The proof dependent key of `ck`, linked to commitments under `coeff_ck`,
+    /// each committing to `coeff_ck.g.len()` consecutive committed witnesses
+    pub fn external_parameters(
+        ck: &gro::CommittingKey<E>,
+        coeff_ck: CommittingKey<E::G1>,
+    ) -> Result<PublicParameters<E::G1>, LinkError> {
+        let (n, d0) = (ck.proof_dependent_g1.len(), coeff_ck.g.len());
+        if n == 0 || d0 == 0 || n % d0 != 0 {
+            return Err(LinkError::MalformedKey);
+        }
+
+        Ok(PublicParameters {
+            poly_ck: CommittingKey {
+                g: ck.proof_dependent_g1.clone(),
+                h: vec![ck.gamma_eta_g1],
+            },
+            coeff_ck,
+        })
+    }
+
+    /// The proof dependent keys of two circuits with the same committed witness,
+    /// each blinded by its own `gamma_eta_g1`
+    pub fn dual_parameters(
+        ck1: &gro::CommittingKey<E>,
+        ck2: &gro::CommittingKey<E>,
+    ) -> Result<dual_eq::PublicParameters<E::G1>, LinkError> {
+        let n = ck1.proof_dependent_g1.len();
+        if n == 0 || n != ck2.proof_dependent_g1.len() {
+            return Err(LinkError::MalformedKey);
+        }
+        let key = |ck: &gro::CommittingKey<E>| CommittingKey {
+            g: ck.proof_dependent_g1.clone(),
+            h: vec![ck.gamma_eta_g1],
+        };
+
+        Ok(dual_eq::PublicParameters {
+            ck1: key(ck1),
+            ck2: key(ck2),
+        })
+    }
+
+    /// `batch_g1` of an old and of a new key, without blinding generators, so that
+    /// a commitment re-targeted to the new key opens to the whole message of the old one
+    pub fn retarget_parameters(
+        old: &gro::CommittingKey<E>,
+        new: &gro::CommittingKey<E>,
+    ) -> Result<dual_eq::PublicParameters<E::G1>, LinkError> {
+        let n = old.batch_g1.len();
+        if n == 0 || n != new.batch_g1.len() {
+            return Err(LinkError::MalformedKey);
+        }
+        let key = |ck: &gro::CommittingKey<E>| CommittingKey {
+            g: ck.batch_g1.clone(),
+            h: vec![],
+        };
+
+        Ok(dual_eq::PublicParameters {
+            ck1: key(old),
+            ck2: key(new),
+        })
+    }
+}
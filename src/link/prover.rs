@@ -0,0 +1,506 @@
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_groth16::r1cs_to_qap::R1CSToQAP;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_std::rand::{CryptoRng, RngCore};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::{
+        commitment::{
+            pedersen::Pedersen, BatchCommitmentError, BatchCommitmentScheme, CommitmentScheme,
+        },
+        protocol::{
+            sigma::SigmaProtocol,
+            transcript::{
+                bind_context, AppendToTranscript, Context, Points, Scalars, TranscriptProtocol,
+            },
+        },
+    },
+    gro::{CCGroth16, CommittingKey, ProvingKey},
+    linker::{
+        am_com_eq,
+        am_eq::{self, AmEq, PublicParameters},
+        comp_am_com_eq::CompAmComEq,
+        dual_eq::{self, DualEq},
+        Error,
+    },
+    snark::CCSNARK,
+};
+
+use super::{
+    BatchLinkedProof, BatchState, ChunkedProof, ChunkedState, CpLink, DualLinkedProof, DualState,
+    Epoch, ExternalLinkedProof, ExternalState, IncrementalProof, IncrementalState, LinkError,
+    Segment,
+};
+
+impl<E: Pairing, QAP: R1CSToQAP> CpLink<E, QAP> {
+    /// Commit to `committed_witness` and to each of `messages` under `batch_g1`.
+    /// The circuit is then built with `state.instance.tau` as its first public input.
+    pub fn commit_batch<R: RngCore + CryptoRng>(
+        ck: &CommittingKey<E>,
+        committed_witness: &[E::ScalarField],
+        messages: &[&[E::ScalarField]],
+        public_inputs: &[E::ScalarField],
+        context: Option<&Context>,
+        rng: &mut R,
+    ) -> Result<BatchState<E>, LinkError> {
+        Self::commit_segment(
+            ck,
+            committed_witness,
+            messages,
+            public_inputs,
+            &Epoch::default(),
+            context,
+            rng,
+        )
+    }
+
+    /// Commit to the next segment of an incremental batch, as `commit_batch` does for a batch.
+    /// The circuit is then built with `[tau, epoch.initial(tau)]` as its first public inputs,
+    /// aggregating the messages from `initial` on, and proved with `prove_segment`.
+    pub fn commit_segment<R: RngCore + CryptoRng>(
+        ck: &CommittingKey<E>,
+        committed_witness: &[E::ScalarField],
+        messages: &[&[E::ScalarField]],
+        public_inputs: &[E::ScalarField],
+        epoch: &Epoch<E::ScalarField>,
+        context: Option<&Context>,
+        rng: &mut R,
+    ) -> Result<BatchState<E>, LinkError> {
+        let commit_timer = start_timer!(|| "CpLink::Commit Batch");
+        let commitment = CCGroth16::<E, QAP>::commit(ck, committed_witness, rng)?;
+
+        let c_hat = Pedersen::<E::G1>::batch_commit(&ck.batch_g1, messages)?;
+        let tau = Self::segment_challenge(epoch, public_inputs, &c_hat, &commitment.cm, context);
+
+        // the linker opens the aggregation from `tau`, the circuit the one from `initial`
+        let witness = Self::batch_witness(ck, messages, tau)?;
+        end_timer!(commit_timer);
+
+        Ok(BatchState {
+            commitment,
+            instance: am_eq::Instance { c_hat, tau },
+            witness,
+        })
+    }
+
+    /// The aggregation of `messages` from `tau`, split as the message generators
+    /// and the last one of `batch_g1`, see `batch_parameters`
+    fn batch_witness(
+        ck: &CommittingKey<E>,
+        messages: &[&[E::ScalarField]],
+        tau: E::ScalarField,
+    ) -> Result<am_eq::Witness<E::G1>, LinkError> {
+        let (aggregation, _) =
+            Pedersen::<E::G1>::scalar_aggregate(messages, ck.batch_g1.len(), tau, None)?;
+        let d = ck.batch_g1.len() - 1;
+
+        Ok(am_eq::Witness {
+            w: aggregation[..d].to_vec(),
+            alpha: aggregation[d..].to_vec(),
+        })
+    }
+
+    /// Commit to a batch split in chunks of `chunk_len` messages, each with its own committed
+    /// witness, so that the proving key is bounded by the chunk rather than the batch.
+    /// The circuit of the chunk `i` is built with `[tau, state.initial(i)]` as its first public
+    /// inputs, aggregating its messages from `initial` on, and all are proved with `prove_chunked`.
+    pub fn commit_chunked<R: RngCore + CryptoRng>(
+        ck: &CommittingKey<E>,
+        committed_witnesses: &[Vec<E::ScalarField>],
+        messages: &[&[E::ScalarField]],
+        chunk_len: usize,
+        public_inputs: &[E::ScalarField],
+        context: Option<&Context>,
+        rng: &mut R,
+    ) -> Result<ChunkedState<E>, LinkError> {
+        let commit_timer = start_timer!(|| "CpLink::Commit Chunked");
+        if chunk_len == 0 || committed_witnesses.is_empty() {
+            return Err(BatchCommitmentError::EmptyBatch.into());
+        }
+        if messages.len() != chunk_len * committed_witnesses.len() {
+            return Err(Error::LengthMismatch {
+                expected: chunk_len * committed_witnesses.len(),
+                found: messages.len(),
+            }
+            .into());
+        }
+
+        let commitments = committed_witnesses
+            .iter()
+            .map(|committed_witness| CCGroth16::<E, QAP>::commit(ck, committed_witness, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let c_hat = Pedersen::<E::G1>::batch_commit(&ck.batch_g1, messages)?;
+        let d = commitments.iter().map(|cm| cm.cm).collect::<Vec<_>>();
+        let tau = Self::chunked_challenge(public_inputs, &c_hat, &d, chunk_len, context)?;
+
+        let witness = Self::batch_witness(ck, messages, tau)?;
+        end_timer!(commit_timer);
+
+        Ok(ChunkedState {
+            chunk_len,
+            commitments,
+            instance: am_eq::Instance { c_hat, tau },
+            witness,
+        })
+    }
+
+    /// The challenge of a chunked batch, hashing its chunks in turn as the segments
+    /// of an incremental batch, so that it binds the proof dependent commitment `d` of each
+    pub fn chunked_challenge(
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        d: &[E::G1Affine],
+        chunk_len: usize,
+        context: Option<&Context>,
+    ) -> Result<E::ScalarField, LinkError> {
+        if chunk_len == 0 || d.is_empty() || c_hat.len() != chunk_len * d.len() {
+            return Err(Error::LengthMismatch {
+                expected: chunk_len * d.len(),
+                found: c_hat.len(),
+            }
+            .into());
+        }
+
+        let epoch = c_hat
+            .chunks(chunk_len)
+            .zip(d)
+            .fold(Epoch::default(), |epoch, (c_hat, d)| {
+                let tau = Self::segment_challenge(&epoch, public_inputs, c_hat, d, context);
+                epoch.extend(tau, chunk_len)
+            });
+
+        Ok(epoch.tau.unwrap_or_default())
+    }
+
+    /// The challenge of `Pedersen::challenge_with_context`, with the challenge of the previous
+    /// segment ahead of `public_inputs`, so that each segment is bound to all those before it
+    pub fn segment_challenge(
+        epoch: &Epoch<E::ScalarField>,
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        d: &E::G1Affine,
+        context: Option<&Context>,
+    ) -> E::ScalarField {
+        let inputs = epoch
+            .tau
+            .iter()
+            .chain(public_inputs)
+            .cloned()
+            .collect::<Vec<_>>();
+        Pedersen::<E::G1>::challenge_with_context(&inputs, c_hat, d, context)
+    }
+
+    pub fn prove_batch<C, T, R>(
+        pk: &ProvingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        circuit: C,
+        state: &BatchState<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<BatchLinkedProof<E>, LinkError>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove Batch");
+        let snark = CCGroth16::<E, QAP>::prove(pk, circuit, &state.commitment, rng)?;
+        let link = AmEq::prove_with_context(
+            pp,
+            &state.instance,
+            &state.witness,
+            context,
+            transcript,
+            rng,
+        )?;
+        end_timer!(prover_timer);
+
+        Ok(BatchLinkedProof { snark, link })
+    }
+
+    /// Prove the circuit of the next segment, committed with `commit_segment` at
+    /// `incremental.epoch`, and keep it with the opening of its aggregate for the linking step
+    pub fn prove_segment<C, R>(
+        pk: &ProvingKey<E>,
+        circuit: C,
+        state: BatchState<E>,
+        incremental: &mut IncrementalState<E>,
+        rng: &mut R,
+    ) -> Result<(), LinkError>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove Segment");
+        let snark = CCGroth16::<E, QAP>::prove(pk, circuit, &state.commitment, rng)?;
+        let (c, _) = Pedersen::<E::G1>::aggregate(&state.instance.c_hat, state.instance.tau, None)?;
+        let segment = Segment {
+            len: state.instance.c_hat.len(),
+            snark,
+            c,
+        };
+        incremental.push(segment, state.witness, state.instance.tau);
+        end_timer!(prover_timer);
+
+        Ok(())
+    }
+
+    /// The linking step of an incremental batch: one linker proof that the aggregates
+    /// of the segments, combined with the powers of `rho`, open as the same combination
+    /// of the segments of `c_hat`, with `rho` drawn once every segment is fixed.
+    /// The segments must start from the first epoch, as the verifier replays them from there.
+    pub fn prove_incremental<T, R>(
+        pp: &PublicParameters<E::G1>,
+        incremental: &IncrementalState<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<IncrementalProof<E>, LinkError>
+    where
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove Incremental");
+        if incremental.segments.is_empty() {
+            return Err(BatchCommitmentError::EmptyBatch.into());
+        }
+        if incremental.start != Epoch::default() {
+            return Err(Error::MalformedProof.into());
+        }
+
+        let aggregates = incremental
+            .segments
+            .iter()
+            .map(|segment| segment.c)
+            .collect::<Vec<_>>();
+        let rho =
+            Self::incremental_challenge(&incremental.epoch, &aggregates, context, transcript)?;
+
+        let d = pp.poly_ck.g.len();
+        let openings = incremental
+            .witnesses
+            .iter()
+            .map(|witness| [&witness.w[..], &witness.alpha].concat())
+            .collect::<Vec<_>>();
+        let slices = openings.iter().map(|m| &m[..]).collect::<Vec<_>>();
+        let (aggregation, _) =
+            Pedersen::<E::G1>::scalar_aggregate(&slices, d + pp.poly_ck.h.len(), rho, None)?;
+        let witness = am_eq::Witness {
+            w: aggregation[..d].to_vec(),
+            alpha: aggregation[d..].to_vec(),
+        };
+        let instance = am_eq::Instance {
+            c_hat: aggregates,
+            tau: rho,
+        };
+        let link = AmEq::prove(pp, &instance, &witness, transcript, rng)?;
+        end_timer!(prover_timer);
+
+        Ok(IncrementalProof {
+            segments: incremental.segments.clone(),
+            link,
+        })
+    }
+
+    /// The challenge combining the segments of an incremental batch, after the context,
+    /// the challenge of the last segment, which is chained to all the others,
+    /// and the aggregates of the segments
+    pub fn incremental_challenge<T: TranscriptProtocol>(
+        epoch: &Epoch<E::ScalarField>,
+        aggregates: &[E::G1Affine],
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<E::ScalarField, LinkError> {
+        let tau = epoch.tau.ok_or(BatchCommitmentError::EmptyBatch)?;
+        bind_context(context, transcript);
+        Scalars(&[tau])
+            .append_to_transcript(b"epoch", transcript)
+            .map_err(Error::from)?;
+        Points(aggregates)
+            .append_to_transcript(b"aggregates", transcript)
+            .map_err(Error::from)?;
+
+        Ok(transcript.challenge_scalar(b"rho"))
+    }
+
+    /// Prove the circuit of each chunk, one at a time, then the final aggregation step:
+    /// the linker proof over the whole batch, of which the chunks are the slices
+    pub fn prove_chunked<C, I, T, R>(
+        pk: &ProvingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        circuits: I,
+        state: &ChunkedState<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<ChunkedProof<E>, LinkError>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        I: IntoIterator<Item = C>,
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove Chunked");
+        let circuits = circuits.into_iter().collect::<Vec<_>>();
+        if circuits.len() != state.commitments.len() {
+            return Err(Error::LengthMismatch {
+                expected: state.commitments.len(),
+                found: circuits.len(),
+            }
+            .into());
+        }
+
+        let chunks = circuits
+            .into_iter()
+            .zip(&state.commitments)
+            .map(|(circuit, commitment)| CCGroth16::<E, QAP>::prove(pk, circuit, commitment, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let link = AmEq::prove_with_context(
+            pp,
+            &state.instance,
+            &state.witness,
+            context,
+            transcript,
+            rng,
+        )?;
+        end_timer!(prover_timer);
+
+        Ok(ChunkedProof { chunks, link })
+    }
+
+    /// Commit to the messages `w`, flattened as the committed witness,
+    /// and to each `w_i` with the blinding `beta_i` under `pp.coeff_ck`
+    pub fn commit_external<R: RngCore + CryptoRng>(
+        ck: &CommittingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        w: Vec<Vec<E::ScalarField>>,
+        beta: Vec<Vec<E::ScalarField>>,
+        rng: &mut R,
+    ) -> Result<ExternalState<E>, LinkError> {
+        let commit_timer = start_timer!(|| "CpLink::Commit External");
+        let commitment = CCGroth16::<E, QAP>::commit(ck, &w.concat(), rng)?;
+
+        let c_hat = cfg_iter!(w)
+            .zip(&beta)
+            .map(|(w_i, beta_i)| {
+                Pedersen::<E::G1>::commit(&pp.coeff_ck.g, w_i)
+                    + Pedersen::<E::G1>::commit(&pp.coeff_ck.h, beta_i)
+            })
+            .collect::<Vec<_>>();
+        let instance = am_com_eq::Instance {
+            c: commitment.cm,
+            c_hat: E::G1::normalize_batch(&c_hat),
+        };
+        let witness = am_com_eq::Witness {
+            w,
+            alpha: vec![commitment.opening],
+            beta,
+        };
+        end_timer!(commit_timer);
+
+        Ok(ExternalState {
+            commitment,
+            instance,
+            witness,
+        })
+    }
+
+    pub fn prove_external<C, T, R>(
+        pk: &ProvingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        circuit: C,
+        state: &ExternalState<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<ExternalLinkedProof<E>, LinkError>
+    where
+        C: ConstraintSynthesizer<E::ScalarField>,
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove External");
+        let snark = CCGroth16::<E, QAP>::prove(pk, circuit, &state.commitment, rng)?;
+        let link = CompAmComEq::prove_with_context(
+            pp,
+            &state.instance,
+            &state.witness,
+            context,
+            transcript,
+            rng,
+        )?;
+        end_timer!(prover_timer);
+
+        Ok(ExternalLinkedProof { snark, link })
+    }
+
+    /// Commit to `committed_witness` under the keys of both circuits
+    pub fn commit_dual<R: RngCore + CryptoRng>(
+        ck1: &CommittingKey<E>,
+        ck2: &CommittingKey<E>,
+        committed_witness: &[E::ScalarField],
+        rng: &mut R,
+    ) -> Result<DualState<E>, LinkError> {
+        let commit_timer = start_timer!(|| "CpLink::Commit Dual");
+        let commitment1 = CCGroth16::<E, QAP>::commit(ck1, committed_witness, rng)?;
+        let commitment2 = CCGroth16::<E, QAP>::commit(ck2, committed_witness, rng)?;
+
+        let instance = dual_eq::Instance {
+            d1: commitment1.cm,
+            d2: commitment2.cm,
+        };
+        let witness = dual_eq::Witness {
+            w: committed_witness.to_vec(),
+            alpha1: vec![commitment1.opening],
+            alpha2: vec![commitment2.opening],
+        };
+        end_timer!(commit_timer);
+
+        Ok(DualState {
+            commitment1,
+            commitment2,
+            instance,
+            witness,
+        })
+    }
+
+    pub fn prove_dual<C1, C2, T, R>(
+        (pk1, pk2): (&ProvingKey<E>, &ProvingKey<E>),
+        pp: &dual_eq::PublicParameters<E::G1>,
+        (circuit1, circuit2): (C1, C2),
+        state: &DualState<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<DualLinkedProof<E>, LinkError>
+    where
+        C1: ConstraintSynthesizer<E::ScalarField>,
+        C2: ConstraintSynthesizer<E::ScalarField>,
+        T: TranscriptProtocol,
+        R: RngCore + CryptoRng,
+    {
+        let prover_timer = start_timer!(|| "CpLink::Prove Dual");
+        let snark1 = CCGroth16::<E, QAP>::prove(pk1, circuit1, &state.commitment1, rng)?;
+        let snark2 = CCGroth16::<E, QAP>::prove(pk2, circuit2, &state.commitment2, rng)?;
+        let link = DualEq::prove_with_context(
+            pp,
+            &state.instance,
+            &state.witness,
+            context,
+            transcript,
+            rng,
+        )?;
+        end_timer!(prover_timer);
+
+        Ok(DualLinkedProof {
+            snark1,
+            snark2,
+            link,
+        })
+    }
+}
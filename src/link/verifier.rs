@@ -0,0 +1,253 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_groth16::r1cs_to_qap::R1CSToQAP;
+use ark_std::{One, Zero};
+
+use crate::{
+    crypto::{
+        commitment::{pedersen::Pedersen, BatchCommitmentError, BatchCommitmentScheme},
+        protocol::{
+            sigma::SigmaProtocol,
+            transcript::{Context, TranscriptProtocol},
+        },
+    },
+    gro::{CCGroth16, VerifyingKey},
+    linker::{
+        am_com_eq,
+        am_eq::{self, AmEq, PublicParameters},
+        comp_am_com_eq::CompAmComEq,
+        dual_eq::{self, DualEq},
+        Error,
+    },
+    snark::CCSNARK,
+};
+
+use super::{
+    BatchLinkedProof, ChunkedProof, CpLink, DualLinkedProof, Epoch, ExternalLinkedProof,
+    IncrementalProof, LinkError,
+};
+
+impl<E: Pairing, QAP: R1CSToQAP> CpLink<E, QAP> {
+    /// Verify the SNARK on `[tau, public_inputs..]`, with `tau` derived from the batch `c_hat`,
+    /// and that its proof dependent commitment is linked to `c_hat`.
+    ///
+    /// The aggregation of `c_hat` added to `D` is the one the linker proof opens,
+    /// so that both proofs speak about the same messages.
+    pub fn verify_batch<T: TranscriptProtocol>(
+        vk: &VerifyingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        proof: &BatchLinkedProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        let verifier_timer = start_timer!(|| "CpLink::Verify Batch");
+        if c_hat.is_empty() {
+            return Err(BatchCommitmentError::EmptyBatch.into());
+        }
+        let tau = Pedersen::<E::G1>::challenge_with_context(
+            public_inputs,
+            c_hat,
+            &proof.snark.d,
+            context,
+        );
+
+        let mut snark = proof.snark.clone();
+        snark.d = (snark.d.into_group() + proof.link.c).into_affine();
+        let inputs = [&[tau], public_inputs].concat();
+        if !CCGroth16::<E, QAP>::verify(vk, &inputs, &snark)? {
+            return Err(LinkError::InvalidSnark);
+        }
+
+        let instance = am_eq::Instance {
+            c_hat: c_hat.to_vec(),
+            tau,
+        };
+        if !AmEq::verify_with_context(pp, &instance, &proof.link, context, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+
+    /// Verify an incremental batch over `c_hat`, all the commitments so far, in one step:
+    /// the SNARK of each segment as in `verify_batch`, on `[tau, initial, public_inputs..]`
+    /// with the aggregate `c` of the segment, then the linking step, that the aggregates
+    /// combined with the powers of `rho` open as the same combination of the segments of `c_hat`.
+    pub fn verify_incremental<T: TranscriptProtocol>(
+        vk: &VerifyingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        proof: &IncrementalProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        let verifier_timer = start_timer!(|| "CpLink::Verify Incremental");
+        if proof.is_empty() || proof.segments.iter().any(|segment| segment.len == 0) {
+            return Err(BatchCommitmentError::EmptyBatch.into());
+        }
+        if proof.len() != c_hat.len() {
+            return Err(Error::LengthMismatch {
+                expected: c_hat.len(),
+                found: proof.len(),
+            }
+            .into());
+        }
+
+        let mut epoch = Epoch::default();
+        let mut aggregates = vec![];
+        for segment in proof.segments.iter() {
+            let c_hat = &c_hat[epoch.len..epoch.len + segment.len];
+            let tau =
+                Self::segment_challenge(&epoch, public_inputs, c_hat, &segment.snark.d, context);
+            let initial = epoch.initial(tau);
+
+            // the circuit aggregates from `initial = tau^(len + 1)`, the aggregate `c` from `tau`
+            let mut snark = segment.snark.clone();
+            let shift = tau.pow([epoch.len as u64]);
+            snark.d = (snark.d.into_group() + segment.c * shift).into_affine();
+            let inputs = [&[tau, initial], public_inputs].concat();
+            if !CCGroth16::<E, QAP>::verify(vk, &inputs, &snark)? {
+                return Err(LinkError::InvalidSnark);
+            }
+
+            aggregates.push(Pedersen::<E::G1>::aggregate(c_hat, tau, None)?.0);
+            epoch = epoch.extend(tau, segment.len);
+        }
+
+        let c = proof
+            .segments
+            .iter()
+            .map(|segment| segment.c)
+            .collect::<Vec<_>>();
+        let rho = Self::incremental_challenge(&epoch, &c, context, transcript)?;
+        if Pedersen::<E::G1>::aggregate(&c, rho, None)?.0 != proof.link.c {
+            return Err(LinkError::InvalidLink);
+        }
+
+        let instance = am_eq::Instance {
+            c_hat: aggregates,
+            tau: rho,
+        };
+        if !AmEq::verify(pp, &instance, &proof.link, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+
+    /// Verify a batch proved in chunks: the SNARK of the chunk `i` on
+    /// `[tau, tau^(i * chunk_len + 1), public_inputs..]` with the aggregate `c_i` of its slice
+    /// of `c_hat`, then the final aggregation step, that the slices shifted to their position
+    /// sum up to the aggregate of `c_hat` that the linker proof opens.
+    pub fn verify_chunked<T: TranscriptProtocol>(
+        vk: &VerifyingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        proof: &ChunkedProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        let verifier_timer = start_timer!(|| "CpLink::Verify Chunked");
+        if c_hat.is_empty() || proof.chunks.is_empty() {
+            return Err(BatchCommitmentError::EmptyBatch.into());
+        }
+        let chunk_len = c_hat.len() / proof.chunks.len();
+        let d = proof.chunks.iter().map(|snark| snark.d).collect::<Vec<_>>();
+        let tau = Self::chunked_challenge(public_inputs, c_hat, &d, chunk_len, context)?;
+
+        let step = tau.pow([chunk_len as u64]);
+        let mut shift = E::ScalarField::one();
+        let mut c = E::G1::zero();
+        for (snark, c_hat) in proof.chunks.iter().zip(c_hat.chunks(chunk_len)) {
+            // the aggregate of the slice, before the shift to its position
+            let c_i = Pedersen::<E::G1>::aggregate(c_hat, tau, None)?.0 * shift;
+            let mut snark = snark.clone();
+            snark.d = (snark.d + c_i).into_affine();
+            let inputs = [&[tau, shift * tau], public_inputs].concat();
+            if !CCGroth16::<E, QAP>::verify(vk, &inputs, &snark)? {
+                return Err(LinkError::InvalidSnark);
+            }
+
+            c += c_i;
+            shift *= step;
+        }
+
+        // the slices must make up the aggregate of the whole batch
+        if c.into_affine() != proof.link.c {
+            return Err(LinkError::InvalidLink);
+        }
+        let instance = am_eq::Instance {
+            c_hat: c_hat.to_vec(),
+            tau,
+        };
+        if !AmEq::verify_with_context(pp, &instance, &proof.link, context, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+
+    /// Verify the SNARK on `public_inputs`, and that its proof dependent commitment
+    /// opens to the messages of `c_hat`
+    pub fn verify_external<T: TranscriptProtocol>(
+        vk: &VerifyingKey<E>,
+        pp: &PublicParameters<E::G1>,
+        public_inputs: &[E::ScalarField],
+        c_hat: &[E::G1Affine],
+        proof: &ExternalLinkedProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        let verifier_timer = start_timer!(|| "CpLink::Verify External");
+        if !CCGroth16::<E, QAP>::verify(vk, public_inputs, &proof.snark)? {
+            return Err(LinkError::InvalidSnark);
+        }
+
+        let instance = am_com_eq::Instance {
+            c: proof.snark.d,
+            c_hat: c_hat.to_vec(),
+        };
+        if !CompAmComEq::verify_with_context(pp, &instance, &proof.link, context, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+
+    /// Verify both SNARKs on their public inputs, and that their proof dependent commitments
+    /// open to the same committed witness
+    pub fn verify_dual<T: TranscriptProtocol>(
+        (vk1, vk2): (&VerifyingKey<E>, &VerifyingKey<E>),
+        pp: &dual_eq::PublicParameters<E::G1>,
+        (public_inputs1, public_inputs2): (&[E::ScalarField], &[E::ScalarField]),
+        proof: &DualLinkedProof<E>,
+        context: Option<&Context>,
+        transcript: &mut T,
+    ) -> Result<(), LinkError> {
+        let verifier_timer = start_timer!(|| "CpLink::Verify Dual");
+        if !CCGroth16::<E, QAP>::verify(vk1, public_inputs1, &proof.snark1)?
+            || !CCGroth16::<E, QAP>::verify(vk2, public_inputs2, &proof.snark2)?
+        {
+            return Err(LinkError::InvalidSnark);
+        }
+
+        let instance = dual_eq::Instance {
+            d1: proof.snark1.d,
+            d2: proof.snark2.d,
+        };
+        if !DualEq::verify_with_context(pp, &instance, &proof.link, context, transcript)? {
+            return Err(LinkError::InvalidLink);
+        }
+        end_timer!(verifier_timer);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
-use crate::solidity::Solidity;
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
 
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CommittingKey<C: CurveGroup> {
@@ -52,6 +55,13 @@ pub struct Instance<C: CurveGroup> {
     pub c_hat: Vec<C::Affine>,
 }
 
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.point(&self.c, dest)?;
+        encoding.points(&self.c_hat, dest)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<C: CurveGroup> {
     pub w: Vec<Vec<C::ScalarField>>,
@@ -82,6 +92,26 @@ where
     }
 }
 
+impl<C: CurveGroup> AppendToTranscript for Commitment<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&[self.a, self.a_hat], dest)
+    }
+}
+
+/// Instance bound to the transcript, with the powers of its aggregation challenge
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Statement<C: CurveGroup> {
+    pub instance: Instance<C>,
+    pub powers_of_x: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Response<C: CurveGroup> {
+    pub z: Vec<C::ScalarField>,
+    pub omega: Vec<C::ScalarField>,
+    pub omega_hat: Vec<C::ScalarField>,
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<C: CurveGroup> {
     pub commitment: Commitment<C>,
@@ -90,6 +120,17 @@ pub struct Proof<C: CurveGroup> {
     pub omega_hat: Vec<C::ScalarField>,
 }
 
+impl<C: CurveGroup> Proof<C> {
+    pub fn from_conversation(commitment: Commitment<C>, response: Response<C>) -> Self {
+        Self {
+            commitment,
+            z: response.z,
+            omega: response.omega,
+            omega_hat: response.omega_hat,
+        }
+    }
+}
+
 impl<C: CurveGroup> Solidity for Proof<C>
 where
     C::Affine: Solidity,
@@ -1,5 +0,0 @@
-#[derive(Debug)]
-pub enum AmComEqError {
-    MismatchParameterLength,
-    NonPowerOfTwo,
-}
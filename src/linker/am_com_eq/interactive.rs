@@ -0,0 +1,118 @@
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+use crate::{
+    crypto::protocol::{
+        channel::{Channel, Party, Status},
+        sigma::{SigmaProver, SigmaVerifier},
+    },
+    linker::Error,
+};
+
+use super::{AmComEq, Instance, PublicParameters, Statement, Witness};
+
+enum Phase<R, M> {
+    /// Waiting for the challenge `x` batching `c_hat`
+    Binding(R),
+    /// The three moves over the statement
+    Sigma(M),
+    Done,
+}
+
+/// Prover of `AmComEq` over a channel.
+/// The verifier moves first with the challenge `x` batching `c_hat`, then the three moves follow.
+pub struct InteractiveProver<'a, C: CurveGroup, R> {
+    pp: &'a PublicParameters<C>,
+    instance: &'a Instance<C>,
+    witness: &'a Witness<C>,
+    phase: Phase<R, SigmaProver<'a, AmComEq<C>, R>>,
+}
+
+impl<'a, C: CurveGroup, R> InteractiveProver<'a, C, R> {
+    pub fn new(
+        pp: &'a PublicParameters<C>,
+        instance: &'a Instance<C>,
+        witness: &'a Witness<C>,
+        rng: R,
+    ) -> Self {
+        Self {
+            pp,
+            instance,
+            witness,
+            phase: Phase::Binding(rng),
+        }
+    }
+}
+
+impl<'a, C: CurveGroup, R: RngCore + CryptoRng> Party for InteractiveProver<'a, C, R> {
+    type Output = ();
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<()>, Error> {
+        let mut sigma = match std::mem::replace(&mut self.phase, Phase::Done) {
+            Phase::Binding(rng) => {
+                channel.bind(b"instance", self.instance)?;
+                let x = channel.receive_challenge(b"x")?;
+                let statement = AmComEq::statement_from_x(self.instance, x);
+                SigmaProver::new(self.pp, statement, self.witness, rng)
+            }
+            Phase::Sigma(sigma) => sigma,
+            Phase::Done => return Ok(Status::Done(())),
+        };
+        let status = sigma.step(channel)?;
+        self.phase = Phase::Sigma(sigma);
+
+        Ok(status)
+    }
+}
+
+/// Verifier of `AmComEq` over a channel, drawing `x` and the challenge with `rng`
+pub struct InteractiveVerifier<'a, C: CurveGroup, R> {
+    pp: &'a PublicParameters<C>,
+    instance: &'a Instance<C>,
+    phase: Phase<R, SigmaVerifier<'a, AmComEq<C>, R>>,
+}
+
+impl<'a, C: CurveGroup, R> InteractiveVerifier<'a, C, R> {
+    pub fn new(pp: &'a PublicParameters<C>, instance: &'a Instance<C>, rng: R) -> Self {
+        Self {
+            pp,
+            instance,
+            phase: Phase::Binding(rng),
+        }
+    }
+}
+
+impl<'a, C: CurveGroup, R: RngCore + CryptoRng> Party for InteractiveVerifier<'a, C, R> {
+    type Output = bool;
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<bool>, Error> {
+        match std::mem::replace(&mut self.phase, Phase::Done) {
+            Phase::Binding(mut rng) => {
+                channel.bind(b"instance", self.instance)?;
+                let x = channel.send_challenge(b"x", &mut rng)?;
+                let statement = AmComEq::statement_from_x(self.instance, x);
+                self.phase = Phase::Sigma(SigmaVerifier::new(self.pp, statement, rng));
+                Ok(Status::Continue)
+            }
+            Phase::Sigma(mut sigma) => {
+                let status = sigma.step(channel)?;
+                self.phase = Phase::Sigma(sigma);
+                Ok(status)
+            }
+            // only left behind by a failed step
+            Phase::Done => Ok(Status::Done(false)),
+        }
+    }
+}
+
+impl<C: CurveGroup> AmComEq<C> {
+    /// The statement once `x` is chosen, as `bind` derives it from the transcript
+    pub fn statement_from_x(instance: &Instance<C>, x: C::ScalarField) -> Statement<C> {
+        Statement {
+            instance: instance.clone(),
+            powers_of_x: Self::powers_of_x(x, instance.c_hat.len()),
+        }
+    }
+}
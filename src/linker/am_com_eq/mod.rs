@@ -4,12 +4,21 @@ use ark_ec::CurveGroup;
 use ark_std::rand::{CryptoRng, RngCore};
 
 pub mod data_structure;
-use crate::crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol};
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, TranscriptProtocol},
+    },
+    linker::Error,
+};
 
 pub use self::data_structure::*;
 
-mod errors;
+mod interactive;
+pub use interactive::{InteractiveProver, InteractiveVerifier};
+
 mod prover;
+mod simulator;
 mod verifier;
 
 pub struct AmComEq<C: CurveGroup> {
@@ -21,9 +30,16 @@ impl<C: CurveGroup> SigmaProtocol for AmComEq<C> {
     type Instance = Instance<C>;
     type Witness = Witness<C>;
     type Proof = Proof<C>;
-    type Error = ();
 
-    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, ()> {
+    type Statement = Statement<C>;
+    type Commitment = Commitment<C>;
+    type State = Randomness<C>;
+    type Challenge = C::ScalarField;
+    type Response = Response<C>;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
         Ok(pp.clone())
     }
 
@@ -33,7 +49,7 @@ impl<C: CurveGroup> SigmaProtocol for AmComEq<C> {
         witness: &Self::Witness,
         transcript: &mut T,
         rng: &mut R,
-    ) -> Result<Self::Proof, ()> {
+    ) -> Result<Self::Proof, Self::Error> {
         Self::create_proof(pp, instance, witness, transcript, rng)
     }
 
@@ -42,7 +58,85 @@ impl<C: CurveGroup> SigmaProtocol for AmComEq<C> {
         instance: &Self::Instance,
         proof: &Self::Proof,
         transcript: &mut T,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Self::Error> {
         Self::verify_proof(pp, instance, proof, transcript)
     }
+
+    fn bind<T: TranscriptProtocol>(
+        _pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        Ok(Statement {
+            instance: instance.clone(),
+            powers_of_x: Self::compute_powers_of_x(instance, transcript)?,
+        })
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        Self::check_witness(&statement.instance, witness)?;
+        Self::create_random_commitment(pp, &statement.powers_of_x, rng)
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        Ok(commitment.append_to_transcript(b"commitment", transcript)?)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        transcript.challenge_scalar(b"challenge")
+    }
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        Self::compute_response(pp, witness, &state, &statement.powers_of_x, *challenge)
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        Self::check_response_shape(
+            pp,
+            &statement.instance,
+            &response.z,
+            &response.omega,
+            &response.omega_hat,
+        )?;
+        let expected = Self::expected_commitment(
+            pp,
+            &statement.instance,
+            &statement.powers_of_x,
+            &response.z,
+            &response.omega,
+            &response.omega_hat,
+            *challenge,
+        );
+
+        Ok(expected == *commitment)
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        Self::simulate_conversation(pp, statement, *challenge, rng)
+    }
 }
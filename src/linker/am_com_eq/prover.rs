@@ -1,13 +1,18 @@
 use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{
+    crypto::protocol::{sigma::prove_with_phases, transcript::TranscriptProtocol},
+    linker::Error,
+};
 
-use super::{AmComEq, Commitment, Instance, Proof, PublicParameters, Randomness, Witness};
+use super::{
+    AmComEq, Commitment, Instance, Proof, PublicParameters, Randomness, Response, Witness,
+};
 
 impl<C: CurveGroup> AmComEq<C> {
     /// Generate a random and compute the commitment
@@ -16,7 +21,7 @@ impl<C: CurveGroup> AmComEq<C> {
         pp: &PublicParameters<C>,
         powers_of_x: &[C::ScalarField],
         rng: &mut impl Rng,
-    ) -> Result<(Randomness<C>, Commitment<C>), ()> {
+    ) -> Result<(Randomness<C>, Commitment<C>), Error> {
         let commit_timer = start_timer!(|| "AmComEq::Commit");
         let ld = pp.poly_ck.g.len();
         let d1 = pp.poly_ck.h.len();
@@ -42,7 +47,7 @@ impl<C: CurveGroup> AmComEq<C> {
         pp: &PublicParameters<C>,
         powers_of_x: &[C::ScalarField],
         random: &Randomness<C>,
-    ) -> Result<Commitment<C>, ()> {
+    ) -> Result<Commitment<C>, Error> {
         let a_timer = start_timer!(|| "Compute A");
         let r = cfg_iter!(random.r)
             .map(|s| s.into_bigint())
@@ -85,14 +90,14 @@ impl<C: CurveGroup> AmComEq<C> {
         })
     }
 
-    pub fn create_proof_with_assignment(
+    /// Compute the responses `z`, `ω` and `Ω` to the challenge
+    pub fn compute_response(
         pp: &PublicParameters<C>,
         witness: &Witness<C>,
         randomness: &Randomness<C>,
-        commitment: &Commitment<C>,
         powers_of_x: &[C::ScalarField],
         challenge: C::ScalarField,
-    ) -> Result<Proof<C>, ()> {
+    ) -> Result<Response<C>, Error> {
         let z_timer = start_timer!(|| "Compute Z");
         let z = cfg_iter!(randomness.r)
             .zip(witness.w.concat())
@@ -126,39 +131,41 @@ impl<C: CurveGroup> AmComEq<C> {
 
         end_timer!(omega_hat_timer);
 
-        Ok(Proof {
-            commitment: commitment.clone(),
+        Ok(Response {
             z,
             omega,
             omega_hat,
         })
     }
 
-    /// Create a proof
-    pub fn create_proof(
+    /// Prove that `c` and the commitments `c_hat` share the witness, with the evaluation
+    /// powers of `x` drawn from the transcript before the commitments `A` and `A_hat`
+    pub fn create_proof<R: RngCore + CryptoRng>(
         pp: &PublicParameters<C>,
         instance: &Instance<C>,
         witness: &Witness<C>,
         transcript: &mut impl TranscriptProtocol,
-        rng: &mut impl Rng,
-    ) -> Result<Proof<C>, ()> {
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
         let proof_timer = start_timer!(|| "AmComEq::Prover");
 
-        let powers_of_x = Self::compute_powers_of_x(instance, transcript);
-        let (randomness, commitment) = Self::create_random_commitment(pp, &powers_of_x, rng)?;
-        let challenge = Self::compute_e(&commitment, transcript);
-
-        let proof = Self::create_proof_with_assignment(
-            pp,
-            witness,
-            &randomness,
-            &commitment,
-            &powers_of_x,
-            challenge,
-        )?;
+        let (commitment, response) =
+            prove_with_phases::<Self, _, _>(pp, instance, witness, transcript, rng)?;
 
         end_timer!(proof_timer);
 
-        Ok(proof)
+        Ok(Proof::from_conversation(commitment, response))
+    }
+
+    /// The witness must open every commitment of the instance
+    pub fn check_witness(instance: &Instance<C>, witness: &Witness<C>) -> Result<(), Error> {
+        if witness.w.len() != instance.c_hat.len() {
+            return Err(Error::LengthMismatch {
+                expected: instance.c_hat.len(),
+                found: witness.w.len(),
+            });
+        }
+
+        Ok(())
     }
 }
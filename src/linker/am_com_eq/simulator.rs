@@ -0,0 +1,49 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::linker::Error;
+
+use super::{AmComEq, Commitment, PublicParameters, Response, Statement};
+
+impl<C: CurveGroup> AmComEq<C> {
+    /// Simulate an accepting conversation for the challenge without the witness,
+    /// by sampling the responses and solving the verification equations for the commitment
+    pub fn simulate_conversation(
+        pp: &PublicParameters<C>,
+        statement: &Statement<C>,
+        challenge: C::ScalarField,
+        rng: &mut impl Rng,
+    ) -> Result<(Commitment<C>, Response<C>), Error> {
+        let simulator_timer = start_timer!(|| "AmComEq::Simulator");
+        let ld = pp.poly_ck.g.len();
+        let d1 = pp.poly_ck.h.len();
+        let d2 = pp.coeff_ck.h.len();
+
+        let response = Response {
+            z: (0..ld).map(|_| C::ScalarField::rand(rng)).collect(),
+            omega: (0..d1).map(|_| C::ScalarField::rand(rng)).collect(),
+            omega_hat: (0..d2).map(|_| C::ScalarField::rand(rng)).collect(),
+        };
+        Self::check_response_shape(
+            pp,
+            &statement.instance,
+            &response.z,
+            &response.omega,
+            &response.omega_hat,
+        )?;
+
+        let commitment = Self::expected_commitment(
+            pp,
+            &statement.instance,
+            &statement.powers_of_x,
+            &response.z,
+            &response.omega,
+            &response.omega_hat,
+            challenge,
+        );
+        end_timer!(simulator_timer);
+
+        Ok((commitment, response))
+    }
+}
@@ -1,12 +1,17 @@
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
-use ark_std::One;
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::{rand::Rng, One};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, TranscriptProtocol},
+    },
+    linker::{batch, Error},
+};
 
 use super::{AmComEq, Commitment, Instance, Proof, PublicParameters};
 
@@ -16,25 +21,51 @@ impl<C: CurveGroup> AmComEq<C> {
         instance: &Instance<C>,
         proof: &Proof<C>,
         transcript: &mut impl TranscriptProtocol,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Error> {
+        Self::check_shape(pp, instance, proof)?;
+
         let verifier_timer = start_timer!(|| "AmComEq::Verifier");
 
-        let powers_of_x = Self::compute_powers_of_x(instance, transcript);
+        let statement = Self::bind(pp, instance, transcript)?;
+        Self::absorb(&proof.commitment, transcript)?;
+        let challenge = Self::challenge(transcript);
+
+        let expected = Self::expected_commitment(
+            pp,
+            instance,
+            &statement.powers_of_x,
+            &proof.z,
+            &proof.omega,
+            &proof.omega_hat,
+            challenge,
+        );
 
-        let challenge = Self::compute_e(&proof.commitment, transcript);
+        end_timer!(verifier_timer);
+
+        Ok(expected == proof.commitment)
+    }
 
+    /// The commitment `(A, A_hat)` for which the responses satisfy both verification equations
+    /// `g^z h^ω = A C^e` and `g_hat^(Σ x^i z_i) h_hat^Ω = A_hat (Π c_hat_i^(x^i))^e`
+    pub fn expected_commitment(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        powers_of_x: &[C::ScalarField],
+        z: &[C::ScalarField],
+        omega: &[C::ScalarField],
+        omega_hat: &[C::ScalarField],
+        challenge: C::ScalarField,
+    ) -> Commitment<C> {
         let single_timer = start_timer!(|| "Single Commitment");
-        let z = cfg_iter!(proof.z)
+        let omega = cfg_iter!(omega)
             .map(|s| s.into_bigint())
             .collect::<Vec<_>>();
-        let omega = cfg_iter!(proof.omega)
-            .map(|s| s.into_bigint())
-            .collect::<Vec<_>>();
-        let s_real =
-            C::msm_bigint(&pp.poly_ck.g, &z[..]) + C::msm_bigint(&pp.poly_ck.h, &omega[..]);
+        let z_bigint = cfg_iter!(z).map(|s| s.into_bigint()).collect::<Vec<_>>();
+        let a = C::msm_bigint(&pp.poly_ck.g, &z_bigint[..])
+            + C::msm_bigint(&pp.poly_ck.h, &omega[..])
+            - instance.c * challenge;
+        drop(z_bigint);
         drop(omega);
-
-        let s_expected = proof.commitment.a + instance.c * challenge;
         end_timer!(single_timer);
 
         let multiple_timer = start_timer!(|| "Multiple Commitment");
@@ -48,66 +79,142 @@ impl<C: CurveGroup> AmComEq<C> {
         let aggregated_z = cfg_iter!(d0_indicies)
             .map(|&j| {
                 cfg_iter!(l_indicies)
-                    .map(|&i| proof.z[d0 * i + j] * powers_of_x[i])
+                    .map(|&i| z[d0 * i + j] * powers_of_x[i])
                     .sum::<C::ScalarField>()
                     .into_bigint()
             })
             .collect::<Vec<_>>();
-        drop(z);
 
-        let omega_hat = cfg_iter!(proof.omega_hat)
+        let omega_hat = cfg_iter!(omega_hat)
             .map(|s| s.into_bigint())
             .collect::<Vec<_>>();
-        let m_real = C::msm_bigint(&pp.coeff_ck.g, &aggregated_z)
-            + C::msm_bigint(&pp.coeff_ck.h, &omega_hat[..]);
-
-        drop(aggregated_z);
-        drop(omega_hat);
-
         let powers_of_x = cfg_iter!(powers_of_x)
             .map(|s| s.into_bigint())
             .collect::<Vec<_>>();
-        let m_expected =
-            proof.commitment.a_hat + C::msm_bigint(&instance.c_hat, &powers_of_x[..]) * challenge;
+        let a_hat = C::msm_bigint(&pp.coeff_ck.g, &aggregated_z)
+            + C::msm_bigint(&pp.coeff_ck.h, &omega_hat[..])
+            - C::msm_bigint(&instance.c_hat, &powers_of_x[..]) * challenge;
 
         end_timer!(multiple_timer);
 
-        end_timer!(verifier_timer);
+        Commitment {
+            a: a.into_affine(),
+            a_hat: a_hat.into_affine(),
+        }
+    }
 
-        assert_eq!(
-            s_real.into_affine(),
-            s_expected.into_affine(),
-            "single commit different"
-        );
-        assert_eq!(
-            m_real.into_affine(),
-            m_expected.into_affine(),
-            "multiple commit different"
+    /// Verify a batch of proofs with one multi-scalar multiplication over both committing keys.
+    /// Every proof draws its own evaluation powers of `x` from its transcript,
+    /// and its two equations are weighted by independent random scalars.
+    pub fn verify_batch<T: TranscriptProtocol>(
+        pp: &PublicParameters<C>,
+        proofs: &[(Instance<C>, Proof<C>, T)],
+        rng: &mut impl Rng,
+    ) -> Result<(), Error> {
+        let verifier_timer = start_timer!(|| "AmComEq::Batch Verifier");
+
+        let ld = pp.poly_ck.g.len();
+        let d0 = pp.coeff_ck.g.len();
+        let d1 = pp.poly_ck.h.len();
+
+        let shared_bases = [
+            &pp.poly_ck.g[..],
+            &pp.poly_ck.h,
+            &pp.coeff_ck.g,
+            &pp.coeff_ck.h,
+        ]
+        .concat();
+        let result = batch::verify_batch::<C, _, _, _, _>(
+            &shared_bases,
+            proofs,
+            rng,
+            |equation, instance, proof, mut transcript, rng| {
+                Self::check_shape(pp, instance, proof)?;
+
+                let powers_of_x = Self::compute_powers_of_x(instance, &mut transcript)?;
+                let challenge = Self::compute_e(&proof.commitment, &mut transcript)?;
+                let rho = C::ScalarField::rand(rng);
+                let sigma = C::ScalarField::rand(rng);
+
+                // rho * (g^z h^ω - A - C^e) + sigma * (g_hat^(Σ x^i z_i) h_hat^Ω - A_hat - (Π c_hat_i^(x^i))^e)
+                let (poly, coeff) = equation.shared.split_at_mut(ld + d1);
+                for (s, z) in poly.iter_mut().zip(proof.z.iter().chain(&proof.omega)) {
+                    *s += rho * z;
+                }
+                for (z, x) in proof.z.chunks(d0.max(1)).zip(&powers_of_x) {
+                    for (s, z) in coeff.iter_mut().zip(z) {
+                        *s += sigma * x * z;
+                    }
+                }
+                for (s, omega_hat) in coeff[d0..].iter_mut().zip(&proof.omega_hat) {
+                    *s += sigma * omega_hat;
+                }
+
+                equation
+                    .bases
+                    .extend([proof.commitment.a, instance.c, proof.commitment.a_hat]);
+                equation.scalars.extend([-rho, -rho * challenge, -sigma]);
+
+                equation.bases.extend(&instance.c_hat);
+                equation
+                    .scalars
+                    .extend(powers_of_x.iter().map(|x| -sigma * challenge * x));
+                Ok(())
+            },
+            |instance, proof, transcript| Self::verify_proof(pp, instance, proof, transcript),
         );
+        end_timer!(verifier_timer);
 
-        Ok(s_real.into_affine() == s_expected.into() && m_real.into_affine() == m_expected.into())
+        result
+    }
+
+    /// The responses must match the lengths of the committing keys
+    pub fn check_shape(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+    ) -> Result<(), Error> {
+        Self::check_response_shape(pp, instance, &proof.z, &proof.omega, &proof.omega_hat)
+    }
+
+    pub fn check_response_shape(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        z: &[C::ScalarField],
+        omega: &[C::ScalarField],
+        omega_hat: &[C::ScalarField],
+    ) -> Result<(), Error> {
+        let l = instance.c_hat.len();
+        let d0 = pp.coeff_ck.g.len();
+        if pp.poly_ck.g.len() != l * d0 {
+            return Err(Error::LengthMismatch {
+                expected: l * d0,
+                found: pp.poly_ck.g.len(),
+            });
+        }
+
+        if z.len() != pp.poly_ck.g.len()
+            || omega.len() != pp.poly_ck.h.len()
+            || omega_hat.len() != pp.coeff_ck.h.len()
+        {
+            return Err(Error::MalformedProof);
+        }
+
+        Ok(())
     }
 
     pub fn compute_powers_of_x<T: TranscriptProtocol>(
         instance: &Instance<C>,
         transcript: &mut T,
-    ) -> Vec<C::ScalarField> {
-        let l = instance.c_hat.len();
-        let instance = vec![&[instance.c][..], &instance.c_hat].concat();
-        let bytes = cfg_iter!(instance)
-            .map(|p| {
-                let mut _bytes = vec![];
-                let (x, y) = p.xy().unwrap();
-                y.serialize_uncompressed(&mut _bytes).unwrap();
-                x.serialize_uncompressed(&mut _bytes).unwrap();
-                _bytes.reverse();
-                _bytes
-            })
-            .flatten()
-            .collect::<Vec<_>>();
-        transcript.append(b"instance", &bytes);
+    ) -> Result<Vec<C::ScalarField>, Error> {
+        instance.append_to_transcript(b"instance", transcript)?;
         let x = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
 
+        Ok(Self::powers_of_x(x, instance.c_hat.len()))
+    }
+
+    /// `(1, x, ..., x^{l-1})`, which batch the `l` commitments of `c_hat`
+    pub fn powers_of_x(x: C::ScalarField, l: usize) -> Vec<C::ScalarField> {
         let mut powers_of_x = vec![];
         let mut curr = C::ScalarField::one();
         for _ in 0..l {
@@ -120,16 +227,8 @@ impl<C: CurveGroup> AmComEq<C> {
     pub fn compute_e<T: TranscriptProtocol>(
         commitment: &Commitment<C>,
         transcript: &mut T,
-    ) -> C::ScalarField {
-        let mut bytes = vec![];
-        let (x, y) = commitment.a.xy().unwrap();
-        let (x_hat, y_hat) = commitment.a_hat.xy().unwrap();
-        y_hat.serialize_uncompressed(&mut bytes).unwrap();
-        x_hat.serialize_uncompressed(&mut bytes).unwrap();
-        y.serialize_uncompressed(&mut bytes).unwrap();
-        x.serialize_uncompressed(&mut bytes).unwrap();
-        bytes.reverse();
-        transcript.append(b"commitment", &bytes);
-        transcript.challenge_scalar::<C::ScalarField>(b"challenge")
+    ) -> Result<C::ScalarField, Error> {
+        commitment.append_to_transcript(b"commitment", transcript)?;
+        Ok(transcript.challenge_scalar::<C::ScalarField>(b"challenge"))
     }
 }
@@ -2,7 +2,10 @@ pub use crate::linker::am_com_eq::data_structure::{Commitment, CommittingKey, Pu
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
-use crate::solidity::Solidity;
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
 
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Instance<C: CurveGroup> {
@@ -10,6 +13,13 @@ pub struct Instance<C: CurveGroup> {
     pub tau: C::ScalarField,
 }
 
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&self.c_hat, dest)?;
+        encoding.scalar(&self.tau, dest)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<C: CurveGroup> {
     pub w: Vec<C::ScalarField>,
@@ -22,6 +32,15 @@ pub struct Randomness<C: CurveGroup> {
     pub beta: Vec<C::ScalarField>,
 }
 
+/// First message of the prover: `c` and the commitment, absorbed together as in `compute_e`
+pub type FirstMessage<C> = (<C as CurveGroup>::Affine, Commitment<C>);
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Response<C: CurveGroup> {
+    pub z: Vec<C::ScalarField>,
+    pub gamma: Vec<C::ScalarField>,
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<C: CurveGroup> {
     // claim that c is committed exactly same (w, alpha) as c_hat
@@ -31,6 +50,17 @@ pub struct Proof<C: CurveGroup> {
     pub gamma: Vec<C::ScalarField>,
 }
 
+impl<C: CurveGroup> Proof<C> {
+    pub fn from_conversation((c, commitment): FirstMessage<C>, response: Response<C>) -> Self {
+        Self {
+            c,
+            commitment,
+            z: response.z,
+            gamma: response.gamma,
+        }
+    }
+}
+
 impl<C: CurveGroup> Solidity for Proof<C>
 where
     C::Affine: Solidity,
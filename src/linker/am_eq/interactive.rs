@@ -0,0 +1,10 @@
+use crate::crypto::protocol::sigma::{SigmaProver, SigmaVerifier};
+
+use super::AmEq;
+
+/// Prover of `AmEq` over a channel, for the instance as the statement.
+/// It moves first, sending `c` with the commitment.
+pub type InteractiveProver<'a, C, R> = SigmaProver<'a, AmEq<C>, R>;
+
+/// Verifier of `AmEq` over a channel, drawing the challenge with `rng`
+pub type InteractiveVerifier<'a, C, R> = SigmaVerifier<'a, AmEq<C>, R>;
@@ -4,11 +4,21 @@ use ark_ec::CurveGroup;
 use ark_std::rand::{CryptoRng, RngCore};
 
 pub mod data_structure;
-use crate::crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol};
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, TranscriptProtocol},
+    },
+    linker::Error,
+};
 
 pub use self::data_structure::*;
 
+mod interactive;
+pub use interactive::{InteractiveProver, InteractiveVerifier};
+
 mod prover;
+mod simulator;
 mod verifier;
 
 pub struct AmEq<C: CurveGroup> {
@@ -20,9 +30,16 @@ impl<C: CurveGroup> SigmaProtocol for AmEq<C> {
     type Instance = Instance<C>;
     type Witness = Witness<C>;
     type Proof = Proof<C>;
-    type Error = ();
 
-    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, ()> {
+    type Statement = Instance<C>;
+    type Commitment = FirstMessage<C>;
+    type State = Randomness<C>;
+    type Challenge = C::ScalarField;
+    type Response = Response<C>;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
         Ok(pp.clone())
     }
 
@@ -32,7 +49,7 @@ impl<C: CurveGroup> SigmaProtocol for AmEq<C> {
         witness: &Self::Witness,
         transcript: &mut T,
         rng: &mut R,
-    ) -> Result<Self::Proof, ()> {
+    ) -> Result<Self::Proof, Self::Error> {
         Self::create_proof(pp, instance, witness, transcript, rng)
     }
 
@@ -41,7 +58,75 @@ impl<C: CurveGroup> SigmaProtocol for AmEq<C> {
         instance: &Self::Instance,
         proof: &Self::Proof,
         transcript: &mut T,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Self::Error> {
         Self::verify_proof(pp, instance, proof, transcript)
     }
+
+    /// Absorb the instance so that composed proofs cannot be replayed against another one.
+    /// The standalone `prove` keeps its transcript and skips this move
+    fn bind<T: TranscriptProtocol>(
+        _pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        instance.append_to_transcript(b"instance", transcript)?;
+        Ok(instance.clone())
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        Self::check_witness(pp, witness)?;
+        let (randomness, commitment) = Self::create_random_commitment(pp, rng)?;
+
+        Ok((randomness, (Self::compute_c(pp, witness), commitment)))
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        Self::append_commitment(&commitment.0, &commitment.1, transcript)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        transcript.challenge_scalar(b"challenge")
+    }
+
+    fn respond(
+        _pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        Self::compute_response(witness, &state, *challenge)
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        Self::check_response_shape(pp, &response.z, &response.gamma)?;
+        let (c, commitment) = commitment;
+        let expected =
+            Self::expected_commitment(pp, statement, c, &response.z, &response.gamma, *challenge);
+
+        Ok(expected == *commitment)
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        Self::simulate_conversation(pp, statement, *challenge, rng)
+    }
 }
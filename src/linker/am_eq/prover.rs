@@ -1,13 +1,16 @@
 use ark_ec::CurveGroup;
 use ark_ff::{PrimeField, UniformRand};
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng, RngCore};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::Error,
+};
 
-use super::{AmEq, Commitment, Instance, Proof, PublicParameters, Randomness, Witness};
+use super::{AmEq, Commitment, Instance, Proof, PublicParameters, Randomness, Response, Witness};
 
 impl<C: CurveGroup> AmEq<C> {
     /// Generate a random and compute the commitment
@@ -15,7 +18,7 @@ impl<C: CurveGroup> AmEq<C> {
     pub fn create_random_commitment(
         pp: &PublicParameters<C>,
         rng: &mut impl Rng,
-    ) -> Result<(Randomness<C>, Commitment<C>), ()> {
+    ) -> Result<(Randomness<C>, Commitment<C>), Error> {
         let commit_timer = start_timer!(|| "AmComEq::Commit");
         let d1 = pp.poly_ck.g.len();
         let d2 = pp.poly_ck.h.len();
@@ -38,7 +41,7 @@ impl<C: CurveGroup> AmEq<C> {
     pub fn create_commitment_from_random(
         pp: &PublicParameters<C>,
         random: &Randomness<C>,
-    ) -> Result<Commitment<C>, ()> {
+    ) -> Result<Commitment<C>, Error> {
         let a_timer = start_timer!(|| "Compute A");
         let r = cfg_iter!(random.r)
             .map(|s| s.into_bigint())
@@ -63,14 +66,12 @@ impl<C: CurveGroup> AmEq<C> {
         })
     }
 
-    pub fn create_proof_with_assignment(
-        _pp: &PublicParameters<C>,
+    /// Compute the responses `z` and `γ` to the challenge
+    pub fn compute_response(
         witness: &Witness<C>,
         randomness: &Randomness<C>,
-        c: &C::Affine,
-        commitment: &Commitment<C>,
         challenge: C::ScalarField,
-    ) -> Result<Proof<C>, ()> {
+    ) -> Result<Response<C>, Error> {
         let z_timer = start_timer!(|| "Compute Z");
         let z = cfg_iter!(randomness.r)
             .zip(&witness.w)
@@ -85,25 +86,32 @@ impl<C: CurveGroup> AmEq<C> {
             .collect();
         end_timer!(gamma_timer);
 
-        Ok(Proof {
-            c: c.clone(),
-            commitment: commitment.clone(),
-            z,
-            gamma,
-        })
+        Ok(Response { z, gamma })
     }
 
-    /// Create a proof
-    pub fn create_proof(
+    /// Create a proof that the two commitments open to the same witness. The instance is
+    /// left out of the transcript, which callers bind themselves
+    pub fn create_proof<R: RngCore + CryptoRng>(
         pp: &PublicParameters<C>,
-        _instance: &Instance<C>,
+        instance: &Instance<C>,
         witness: &Witness<C>,
         transcript: &mut impl TranscriptProtocol,
-        rng: &mut impl Rng,
-    ) -> Result<Proof<C>, ()> {
-        let proof_timer = start_timer!(|| "AmComEq::Prover");
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let proof_timer = start_timer!(|| "AmEq::Prover");
+
+        let (state, commitment) = Self::commit(pp, instance, witness, rng)?;
+        Self::absorb(&commitment, transcript)?;
+        let challenge = Self::challenge(transcript);
+        let response = Self::respond(pp, instance, witness, state, &challenge)?;
+
+        end_timer!(proof_timer);
 
-        let (randomness, commitment) = Self::create_random_commitment(pp, rng)?;
+        Ok(Proof::from_conversation(commitment, response))
+    }
+
+    /// Commitment `c = g^w h^α` to the witness under the single committing key
+    pub fn compute_c(pp: &PublicParameters<C>, witness: &Witness<C>) -> C::Affine {
         let c_timer = start_timer!(|| "Compute C");
         let w = cfg_iter!(witness.w)
             .map(|s| s.into_bigint())
@@ -114,20 +122,18 @@ impl<C: CurveGroup> AmEq<C> {
         let c = C::msm_bigint(&pp.poly_ck.g, &w[..]) + C::msm_bigint(&pp.poly_ck.h, &alpha[..]);
         end_timer!(c_timer);
 
-        let c = c.into();
-        let challenge = Self::compute_e(&c, &commitment, transcript);
-
-        let proof = Self::create_proof_with_assignment(
-            pp,
-            witness,
-            &randomness,
-            &c,
-            &commitment,
-            challenge,
-        )?;
+        c.into()
+    }
 
-        end_timer!(proof_timer);
+    /// The witness must match the lengths of the committing key
+    pub fn check_witness(pp: &PublicParameters<C>, witness: &Witness<C>) -> Result<(), Error> {
+        if witness.w.len() != pp.poly_ck.g.len() || witness.alpha.len() != pp.poly_ck.h.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.poly_ck.g.len() + pp.poly_ck.h.len(),
+                found: witness.w.len() + witness.alpha.len(),
+            });
+        }
 
-        Ok(proof)
+        Ok(())
     }
 }
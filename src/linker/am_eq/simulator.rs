@@ -0,0 +1,36 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::linker::Error;
+
+use super::{AmEq, FirstMessage, Instance, PublicParameters, Response};
+
+impl<C: CurveGroup> AmEq<C> {
+    /// Simulate an accepting conversation for the challenge without the witness.
+    /// `c` is a uniformly random point, as is the hiding commitment of an honest prover,
+    /// and the commitment is solved from the verification equations for random responses.
+    pub fn simulate_conversation(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        challenge: C::ScalarField,
+        rng: &mut impl Rng,
+    ) -> Result<(FirstMessage<C>, Response<C>), Error> {
+        let simulator_timer = start_timer!(|| "AmEq::Simulator");
+        let d1 = pp.poly_ck.g.len();
+        let d2 = pp.poly_ck.h.len();
+
+        let c = C::rand(rng).into_affine();
+        let response = Response {
+            z: (0..d1).map(|_| C::ScalarField::rand(rng)).collect(),
+            gamma: (0..d2).map(|_| C::ScalarField::rand(rng)).collect(),
+        };
+        Self::check_response_shape(pp, &response.z, &response.gamma)?;
+
+        let commitment =
+            Self::expected_commitment(pp, instance, &c, &response.z, &response.gamma, challenge);
+        end_timer!(simulator_timer);
+
+        Ok(((c, commitment), response))
+    }
+}
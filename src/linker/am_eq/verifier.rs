@@ -1,11 +1,17 @@
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::rand::Rng;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, TranscriptProtocol},
+    },
+    linker::{batch, Error},
+};
 
 use super::{AmEq, Commitment, Instance, Proof, PublicParameters};
 
@@ -15,29 +21,42 @@ impl<C: CurveGroup> AmEq<C> {
         instance: &Instance<C>,
         proof: &Proof<C>,
         transcript: &mut impl TranscriptProtocol,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Error> {
+        Self::check_shape(pp, proof)?;
+
         let verifier_timer = start_timer!(|| "AmComEq::Verifier");
 
-        let challenge = Self::compute_e(&proof.c, &proof.commitment, transcript);
+        Self::append_commitment(&proof.c, &proof.commitment, transcript)?;
+        let challenge = Self::challenge(transcript);
+        let expected =
+            Self::expected_commitment(pp, instance, &proof.c, &proof.z, &proof.gamma, challenge);
+
+        end_timer!(verifier_timer);
 
+        Ok(expected == proof.commitment)
+    }
+
+    /// The commitment `(A, A_hat)` for which the responses satisfy both verification equations
+    /// `g^z h^γ = A c^e` and `g_hat^z h_hat^γ = A_hat (Π c_hat_i^(τ^i))^e`
+    pub fn expected_commitment(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        c: &C::Affine,
+        z: &[C::ScalarField],
+        gamma: &[C::ScalarField],
+        challenge: C::ScalarField,
+    ) -> Commitment<C> {
         let single_timer = start_timer!(|| "Single Commitment");
-        let z = cfg_iter!(proof.z)
-            .map(|s| s.into_bigint())
-            .collect::<Vec<_>>();
-        let gamma = cfg_iter!(proof.gamma)
+        let z = cfg_iter!(z).map(|s| s.into_bigint()).collect::<Vec<_>>();
+        let gamma = cfg_iter!(gamma)
             .map(|s| s.into_bigint())
             .collect::<Vec<_>>();
-        let s_real =
-            C::msm_bigint(&pp.poly_ck.g, &z[..]) + C::msm_bigint(&pp.poly_ck.h, &gamma[..]);
-
-        let s_expected = proof.commitment.a + proof.c * challenge;
+        let a = C::msm_bigint(&pp.poly_ck.g, &z[..]) + C::msm_bigint(&pp.poly_ck.h, &gamma[..])
+            - *c * challenge;
         end_timer!(single_timer);
 
         let multiple_timer = start_timer!(|| "Multiple Commitment");
 
-        let m_real =
-            C::msm_bigint(&pp.coeff_ck.g, &z[..]) + C::msm_bigint(&pp.coeff_ck.h, &gamma[..]);
-
         let l = instance.c_hat.len();
         let powers_of_tau = {
             let mut acc = vec![];
@@ -50,47 +69,119 @@ impl<C: CurveGroup> AmEq<C> {
         };
         let m_aggregated = C::msm_bigint(&instance.c_hat, &powers_of_tau[..]);
 
-        let m_expected = proof.commitment.a_hat + m_aggregated * challenge;
+        let a_hat = C::msm_bigint(&pp.coeff_ck.g, &z[..])
+            + C::msm_bigint(&pp.coeff_ck.h, &gamma[..])
+            - m_aggregated * challenge;
 
         end_timer!(multiple_timer);
 
-        end_timer!(verifier_timer);
+        Commitment {
+            a: a.into_affine(),
+            a_hat: a_hat.into_affine(),
+        }
+    }
 
-        assert_eq!(
-            s_real.into_affine(),
-            s_expected.into_affine(),
-            "single commit different"
-        );
-        assert_eq!(
-            m_real.into_affine(),
-            m_expected.into_affine(),
-            "multiple commit different"
+    /// Verify a batch of proofs with one multi-scalar multiplication over both committing keys.
+    /// The equations against `c` and against the `tau`-combination of `c_hat`
+    /// are weighted by independent random scalars for every proof.
+    /// Each transcript is the verifier transcript of its proof.
+    pub fn verify_batch<T: TranscriptProtocol>(
+        pp: &PublicParameters<C>,
+        proofs: &[(Instance<C>, Proof<C>, T)],
+        rng: &mut impl Rng,
+    ) -> Result<(), Error> {
+        let verifier_timer = start_timer!(|| "AmEq::Batch Verifier");
+
+        let d1 = pp.poly_ck.g.len();
+        let d2 = pp.poly_ck.h.len();
+
+        let shared_bases = [
+            &pp.poly_ck.g[..],
+            &pp.poly_ck.h,
+            &pp.coeff_ck.g,
+            &pp.coeff_ck.h,
+        ]
+        .concat();
+        let result = batch::verify_batch::<C, _, _, _, _>(
+            &shared_bases,
+            proofs,
+            rng,
+            |equation, instance, proof, mut transcript, rng| {
+                Self::check_shape(pp, proof)?;
+
+                let challenge = Self::compute_e(&proof.c, &proof.commitment, &mut transcript)?;
+                let rho = C::ScalarField::rand(rng);
+                let sigma = C::ScalarField::rand(rng);
+
+                // rho * (g^z h^gamma - A - c^e) + sigma * (g_hat^z h_hat^gamma - A_hat - (Π c_hat_i^(tau^i))^e)
+                for (j, s) in proof.z.iter().chain(&proof.gamma).enumerate() {
+                    equation.shared[j] += rho * s;
+                    equation.shared[d1 + d2 + j] += sigma * s;
+                }
+
+                equation
+                    .bases
+                    .extend([proof.commitment.a, proof.c, proof.commitment.a_hat]);
+                equation.scalars.extend([-rho, -rho * challenge, -sigma]);
+
+                let mut curr = instance.tau;
+                for c_hat in instance.c_hat.iter() {
+                    equation.bases.push(*c_hat);
+                    equation.scalars.push(-sigma * challenge * curr);
+                    curr *= instance.tau;
+                }
+                Ok(())
+            },
+            |instance, proof, transcript| Self::verify_proof(pp, instance, proof, transcript),
         );
+        end_timer!(verifier_timer);
+
+        result
+    }
+
+    /// The responses must match the lengths of the committing keys
+    pub fn check_shape(pp: &PublicParameters<C>, proof: &Proof<C>) -> Result<(), Error> {
+        Self::check_response_shape(pp, &proof.z, &proof.gamma)
+    }
 
-        Ok(s_real.into_affine() == s_expected.into() && m_real.into_affine() == m_expected.into())
+    pub fn check_response_shape(
+        pp: &PublicParameters<C>,
+        z: &[C::ScalarField],
+        gamma: &[C::ScalarField],
+    ) -> Result<(), Error> {
+        if pp.coeff_ck.g.len() != pp.poly_ck.g.len() || pp.coeff_ck.h.len() != pp.poly_ck.h.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.poly_ck.g.len() + pp.poly_ck.h.len(),
+                found: pp.coeff_ck.g.len() + pp.coeff_ck.h.len(),
+            });
+        }
+        if z.len() != pp.poly_ck.g.len() || gamma.len() != pp.poly_ck.h.len() {
+            return Err(Error::MalformedProof);
+        }
+
+        Ok(())
     }
 
     pub fn compute_e<T: TranscriptProtocol>(
         c: &C::Affine,
         commitment: &Commitment<C>,
         transcript: &mut T,
-    ) -> C::ScalarField {
-        let mut bytes = vec![];
+    ) -> Result<C::ScalarField, Error> {
+        Self::append_commitment(c, commitment, transcript)?;
+        Ok(transcript.challenge_scalar::<C::ScalarField>(b"challenge"))
+    }
 
-        // Append the commitment
-        let (x, y) = commitment.a.xy().unwrap();
-        let (x_hat, y_hat) = commitment.a_hat.xy().unwrap();
-        y_hat.serialize_uncompressed(&mut bytes).unwrap();
-        x_hat.serialize_uncompressed(&mut bytes).unwrap();
-        y.serialize_uncompressed(&mut bytes).unwrap();
-        x.serialize_uncompressed(&mut bytes).unwrap();
-
-        // Append the c
-        let (x, y) = c.xy().unwrap();
-        y.serialize_uncompressed(&mut bytes).unwrap();
-        x.serialize_uncompressed(&mut bytes).unwrap();
-        bytes.reverse();
+    pub fn append_commitment<T: TranscriptProtocol>(
+        c: &C::Affine,
+        commitment: &Commitment<C>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        // c, then the commitment
+        let encoding = transcript.encoding();
+        let mut bytes = vec![];
+        encoding.point(c, &mut bytes)?;
+        commitment.encode(encoding, &mut bytes)?;
         transcript.append(b"commitment", &bytes);
-        transcript.challenge_scalar::<C::ScalarField>(b"challenge")
+        Ok(())
     }
 }
@@ -0,0 +1,60 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_std::Zero;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{crypto::protocol::transcript::TranscriptProtocol, linker::Error};
+
+/// The verification equations of a batch, accumulated as one multi-scalar multiplication
+pub struct BatchEquation<C: CurveGroup> {
+    /// Scalars of the bases shared by every proof, in the order they were given
+    pub shared: Vec<C::ScalarField>,
+    /// Bases specific to a single proof
+    pub bases: Vec<C::Affine>,
+    /// Scalars of `bases`
+    pub scalars: Vec<C::ScalarField>,
+}
+
+/// Check a batch of proofs with a single multi-scalar multiplication.
+/// `accumulate` adds the equations of one proof, each weighted by a fresh random scalar,
+/// given a clone of its verifier transcript.
+/// If the batch does not verify, the proofs are checked one by one with `verify`
+/// and the index of the first invalid proof is reported.
+pub fn verify_batch<C, I, P, T, R>(
+    shared_bases: &[C::Affine],
+    proofs: &[(I, P, T)],
+    rng: &mut R,
+    mut accumulate: impl FnMut(&mut BatchEquation<C>, &I, &P, T, &mut R) -> Result<(), Error>,
+    verify: impl Fn(&I, &P, &mut T) -> Result<bool, Error>,
+) -> Result<(), Error>
+where
+    C: CurveGroup,
+    T: TranscriptProtocol,
+{
+    let mut equation = BatchEquation {
+        shared: vec![C::ScalarField::zero(); shared_bases.len()],
+        bases: vec![],
+        scalars: vec![],
+    };
+    for (instance, proof, transcript) in proofs.iter() {
+        accumulate(&mut equation, instance, proof, transcript.clone(), rng)?;
+    }
+
+    let bases = [shared_bases, &equation.bases].concat();
+    let scalars = cfg_iter!(equation.shared)
+        .chain(cfg_iter!(equation.scalars))
+        .map(|s| s.into_bigint())
+        .collect::<Vec<_>>();
+    if C::msm_bigint(&bases, &scalars).is_zero() {
+        return Ok(());
+    }
+
+    for (index, (instance, proof, transcript)) in proofs.iter().enumerate() {
+        if !verify(instance, proof, &mut transcript.clone())? {
+            return Err(Error::InvalidProof(index));
+        }
+    }
+    Ok(())
+}
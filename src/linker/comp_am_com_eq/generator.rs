@@ -4,6 +4,8 @@ use ark_ff::PrimeField;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use crate::linker::Error;
+
 use super::{data_structure::*, CompAmComEq};
 
 impl<C: CurveGroup> CompAmComEq<C> {
@@ -19,7 +21,7 @@ impl<C: CurveGroup> CompAmComEq<C> {
             RecursionInstance<C>,
             RecursionWitness<C>,
         ),
-        (),
+        Error,
     > {
         let prepare_timer = start_timer!(|| "Prepare for CompDLEq");
 
@@ -0,0 +1,171 @@
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+use crate::{
+    crypto::protocol::{
+        channel::{Channel, Party, Status},
+        sigma::SigmaProtocol,
+    },
+    linker::{
+        am_com_eq::{AmComEq, Commitment, Randomness, Statement},
+        comp_dl_eq, Error,
+    },
+};
+
+use super::{ACEProof, CompAmComEq, Instance, PublicParameters, Witness};
+
+enum ProverState<C: CurveGroup, R> {
+    Start(R),
+    Committed(Statement<C>, Randomness<C>, Commitment<C>),
+    Folding(comp_dl_eq::InteractiveProver<C>),
+    Done,
+}
+
+/// Prover of `CompAmComEq` over a channel.
+/// The moves are those of `AmComEq`, except that only `omega` and `omega_hat` of the response
+/// are sent, and `z` goes through the folding of `CompDLEq`.
+pub struct InteractiveProver<'a, C: CurveGroup, R> {
+    pp: &'a PublicParameters<C>,
+    instance: &'a Instance<C>,
+    witness: &'a Witness<C>,
+    state: ProverState<C, R>,
+}
+
+impl<'a, C: CurveGroup, R> InteractiveProver<'a, C, R> {
+    pub fn new(
+        pp: &'a PublicParameters<C>,
+        instance: &'a Instance<C>,
+        witness: &'a Witness<C>,
+        rng: R,
+    ) -> Self {
+        Self {
+            pp,
+            instance,
+            witness,
+            state: ProverState::Start(rng),
+        }
+    }
+}
+
+impl<'a, C: CurveGroup, R: RngCore + CryptoRng> Party for InteractiveProver<'a, C, R> {
+    type Output = ();
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<()>, Error> {
+        let mut folding = match std::mem::replace(&mut self.state, ProverState::Done) {
+            ProverState::Start(mut rng) => {
+                channel.bind(b"instance", self.instance)?;
+                let x = channel.receive_challenge(b"x")?;
+                let statement = AmComEq::statement_from_x(self.instance, x);
+                let (state, commitment) =
+                    AmComEq::commit(self.pp, &statement, self.witness, &mut rng)?;
+                channel.send(b"commitment", &commitment)?;
+                self.state = ProverState::Committed(statement, state, commitment);
+                return Ok(Status::Continue);
+            }
+            ProverState::Committed(statement, state, commitment) => {
+                let challenge = channel.receive_challenge(b"challenge")?;
+                let response =
+                    AmComEq::respond(self.pp, &statement, self.witness, state, &challenge)?;
+                channel.send(b"omega", &response.omega)?;
+                channel.send(b"omega_hat", &response.omega_hat)?;
+
+                let ace_proof = ACEProof::from_conversation(commitment, response);
+                let (pp, instance, witness) = CompAmComEq::prepare_for_comp_dl_eq(
+                    self.pp,
+                    self.instance,
+                    &ace_proof,
+                    &statement.powers_of_x,
+                    challenge,
+                )?;
+                comp_dl_eq::InteractiveProver::new((&pp).into(), (&instance).into(), witness, 2)?
+            }
+            ProverState::Folding(folding) => folding,
+            ProverState::Done => return Ok(Status::Done(())),
+        };
+        let status = folding.step(channel)?;
+        self.state = ProverState::Folding(folding);
+
+        Ok(status)
+    }
+}
+
+enum VerifierState<C: CurveGroup, R> {
+    Start(R),
+    Bound(Statement<C>, R),
+    Challenged(Statement<C>, Commitment<C>, C::ScalarField, R),
+    Folding(comp_dl_eq::InteractiveVerifier<C, R>),
+    Done,
+}
+
+/// Verifier of `CompAmComEq` over a channel, drawing `x` and the challenges with `rng`
+pub struct InteractiveVerifier<'a, C: CurveGroup, R> {
+    pp: &'a PublicParameters<C>,
+    instance: &'a Instance<C>,
+    state: VerifierState<C, R>,
+}
+
+impl<'a, C: CurveGroup, R> InteractiveVerifier<'a, C, R> {
+    pub fn new(pp: &'a PublicParameters<C>, instance: &'a Instance<C>, rng: R) -> Self {
+        Self {
+            pp,
+            instance,
+            state: VerifierState::Start(rng),
+        }
+    }
+}
+
+impl<'a, C: CurveGroup, R: RngCore + CryptoRng> Party for InteractiveVerifier<'a, C, R> {
+    type Output = bool;
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<bool>, Error> {
+        let mut folding = match std::mem::replace(&mut self.state, VerifierState::Done) {
+            VerifierState::Start(mut rng) => {
+                channel.bind(b"instance", self.instance)?;
+                let x = channel.send_challenge(b"x", &mut rng)?;
+                let statement = AmComEq::statement_from_x(self.instance, x);
+                self.state = VerifierState::Bound(statement, rng);
+                return Ok(Status::Continue);
+            }
+            VerifierState::Bound(statement, mut rng) => {
+                let commitment = channel.receive(b"commitment")?;
+                let challenge = channel.send_challenge(b"challenge", &mut rng)?;
+                self.state = VerifierState::Challenged(statement, commitment, challenge, rng);
+                return Ok(Status::Continue);
+            }
+            VerifierState::Challenged(statement, commitment, challenge, rng) => {
+                let omega: Vec<_> = channel.receive(b"omega")?;
+                let omega_hat: Vec<_> = channel.receive(b"omega_hat")?;
+                if omega.len() != self.pp.poly_ck.h.len()
+                    || omega_hat.len() != self.pp.coeff_ck.h.len()
+                {
+                    return Err(Error::MalformedProof);
+                }
+
+                // `z` is only known once the folding is over, and the instance does not need it
+                let ace_proof = ACEProof {
+                    commitment,
+                    z: vec![],
+                    omega,
+                    omega_hat,
+                };
+                let (pp, instance, _) = CompAmComEq::prepare_for_comp_dl_eq(
+                    self.pp,
+                    self.instance,
+                    &ace_proof,
+                    &statement.powers_of_x,
+                    challenge,
+                )?;
+                comp_dl_eq::InteractiveVerifier::new((&pp).into(), (&instance).into(), 2, rng)?
+            }
+            VerifierState::Folding(folding) => folding,
+            // only left behind by a failed step
+            VerifierState::Done => return Ok(Status::Done(false)),
+        };
+        let status = folding.step(channel)?;
+        self.state = VerifierState::Folding(folding);
+
+        Ok(status)
+    }
+}
@@ -7,11 +7,18 @@ mod data_structure;
 pub use data_structure::*;
 
 mod generator;
+mod interactive;
+pub use interactive::{InteractiveProver, InteractiveVerifier};
+
 mod prover;
 mod verifier;
 
-use crate::crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol};
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_com_eq::AmComEq, Error},
+};
 
+/// The three moves are those of `AmComEq`; only `prove` compresses the response.
 pub struct CompAmComEq<C: CurveGroup> {
     _group: PhantomData<C>,
 }
@@ -21,9 +28,16 @@ impl<C: CurveGroup> SigmaProtocol for CompAmComEq<C> {
     type Instance = Instance<C>;
     type Witness = Witness<C>;
     type Proof = Proof<C>;
-    type Error = ();
 
-    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, ()> {
+    type Statement = <AmComEq<C> as SigmaProtocol>::Statement;
+    type Commitment = <AmComEq<C> as SigmaProtocol>::Commitment;
+    type State = <AmComEq<C> as SigmaProtocol>::State;
+    type Challenge = C::ScalarField;
+    type Response = <AmComEq<C> as SigmaProtocol>::Response;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
         Ok(pp.clone())
     }
 
@@ -33,7 +47,7 @@ impl<C: CurveGroup> SigmaProtocol for CompAmComEq<C> {
         witness: &Self::Witness,
         transcript: &mut T,
         rng: &mut R,
-    ) -> Result<Self::Proof, ()> {
+    ) -> Result<Self::Proof, Self::Error> {
         Self::creat_proof_with_combined(pp, instance, witness, transcript, rng)
     }
 
@@ -42,7 +56,64 @@ impl<C: CurveGroup> SigmaProtocol for CompAmComEq<C> {
         instance: &Self::Instance,
         proof: &Self::Proof,
         transcript: &mut T,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Self::Error> {
         Self::verify_proof(pp, instance, proof, transcript)
     }
+
+    fn bind<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        AmComEq::bind(pp, instance, transcript)
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        AmComEq::commit(pp, statement, witness, rng)
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        AmComEq::<C>::absorb(commitment, transcript)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        AmComEq::<C>::challenge(transcript)
+    }
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        AmComEq::respond(pp, statement, witness, state, challenge)
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        AmComEq::check(pp, statement, commitment, challenge, response)
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        AmComEq::simulate(pp, statement, challenge, rng)
+    }
 }
@@ -2,8 +2,8 @@ use ark_ec::CurveGroup;
 use ark_std::rand::{CryptoRng, RngCore};
 
 use crate::{
-    crypto::protocol::transcript::TranscriptProtocol,
-    linker::{am_com_eq::AmComEq, comp_dl_eq::CompDLEq},
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_com_eq::AmComEq, comp_dl_eq::CompDLEq, Error},
 };
 
 use super::{data_structure::*, CompAmComEq};
@@ -15,23 +15,23 @@ impl<C: CurveGroup> CompAmComEq<C> {
         witness: &Witness<C>,
         transcript: &mut impl TranscriptProtocol,
         rng: &mut R,
-    ) -> Result<Proof<C>, ()> {
+    ) -> Result<Proof<C>, Error> {
         let prover_timer = start_timer!(|| "CompAmComEq::Prover");
 
-        let powers_of_x = AmComEq::compute_powers_of_x(instance, transcript);
-        let (randomness, commitment) = AmComEq::create_random_commitment(pp, &powers_of_x, rng)?;
-        let challenge = AmComEq::compute_e(&commitment, transcript);
-        let ace_proof = AmComEq::create_proof_with_assignment(
+        let statement = AmComEq::bind(pp, instance, transcript)?;
+        let (state, commitment) = AmComEq::commit(pp, &statement, witness, rng)?;
+        AmComEq::absorb(&commitment, transcript)?;
+        let challenge = AmComEq::<C>::challenge(transcript);
+        let response = AmComEq::respond(pp, &statement, witness, state, &challenge)?;
+        let ace_proof = ACEProof::from_conversation(commitment.clone(), response);
+
+        let (pp, instance, witness) = Self::prepare_for_comp_dl_eq(
             pp,
-            witness,
-            &randomness,
-            &commitment,
-            &powers_of_x,
+            instance,
+            &ace_proof,
+            &statement.powers_of_x,
             challenge,
         )?;
-
-        let (pp, instance, witness) =
-            Self::prepare_for_comp_dl_eq(pp, instance, &ace_proof, &powers_of_x, challenge)?;
         let cde_proof = CompDLEq::create_proof(&pp, &instance, &witness, transcript)?;
 
         end_timer!(prover_timer);
@@ -1,8 +1,8 @@
 use ark_ec::CurveGroup;
 
 use crate::{
-    crypto::protocol::transcript::TranscriptProtocol,
-    linker::{am_com_eq::AmComEq, comp_dl_eq::CompDLEq},
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_com_eq::AmComEq, comp_dl_eq::CompDLEq, Error},
 };
 
 use super::{CDEProof, CompAmComEq, Instance, Proof, PublicParameters};
@@ -13,18 +13,27 @@ impl<C: CurveGroup> CompAmComEq<C> {
         instance: &Instance<C>,
         proof: &Proof<C>,
         transcript: &mut T,
-    ) -> Result<bool, ()> {
-        if proof.ace.z.len() != 2 {
-            return Err(());
+    ) -> Result<bool, Error> {
+        if proof.ace.z.len() != 2
+            || proof.ace.omega.len() != pp.poly_ck.h.len()
+            || proof.ace.omega_hat.len() != pp.coeff_ck.h.len()
+        {
+            return Err(Error::MalformedProof);
         }
 
         let verifier_timer = start_timer!(|| "CompAmComEq::Verify");
 
-        let powers_of_x = AmComEq::compute_powers_of_x(instance, transcript);
-        let challenge = AmComEq::compute_e(&proof.ace.commitment, transcript);
-
-        let (pp, instance, witness) =
-            Self::prepare_for_comp_dl_eq(pp, instance, &proof.ace, &powers_of_x, challenge)?;
+        let statement = AmComEq::bind(pp, instance, transcript)?;
+        AmComEq::absorb(&proof.ace.commitment, transcript)?;
+        let challenge = AmComEq::<C>::challenge(transcript);
+
+        let (pp, instance, witness) = Self::prepare_for_comp_dl_eq(
+            pp,
+            instance,
+            &proof.ace,
+            &statement.powers_of_x,
+            challenge,
+        )?;
 
         let cde_proof = CDEProof {
             commitments: proof.commitments.clone(),
@@ -0,0 +1,35 @@
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+pub use crate::linker::am_eq::{Instance, PublicParameters, Witness};
+pub(super) use crate::linker::{
+    am_eq::Proof as AEProof,
+    comp_dl_eq::{
+        Commitment as RecursionCommitment, Instance as RecursionInstance, Proof as CDEProof,
+        PublicParameters as RecursionPublicParameters, Witness as RecursionWitness,
+    },
+};
+use crate::solidity::Solidity;
+
+/// Public parameters, instance and witness of the CompDLEq folding
+pub(super) type Recursion<C> = (
+    RecursionPublicParameters<C>,
+    RecursionInstance<C>,
+    RecursionWitness<C>,
+);
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<C: CurveGroup> {
+    pub commitments: Vec<RecursionCommitment<C>>,
+    pub ae: AEProof<C>,
+}
+
+impl<C: CurveGroup> Solidity for Proof<C>
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [self.ae.to_solidity(), self.commitments.to_solidity()].concat()
+    }
+}
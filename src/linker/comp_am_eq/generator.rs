@@ -0,0 +1,62 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::linker::Error;
+
+use super::{data_structure::*, CompAmEq};
+
+impl<C: CurveGroup> CompAmEq<C> {
+    pub fn prepare_for_comp_dl_eq(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &AEProof<C>,
+        challenge: C::ScalarField,
+    ) -> Result<Recursion<C>, Error> {
+        let prepare_timer = start_timer!(|| "Prepare for CompDLEq");
+
+        let g = pp.poly_ck.g.clone();
+        let g_hat = pp.coeff_ck.g.clone();
+
+        let expected_timer = start_timer!(|| "Compute Expected");
+
+        let gamma = cfg_iter!(proof.gamma)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+
+        let y_timer = start_timer!(|| "Compute Y");
+        let y = proof.commitment.a + proof.c * challenge - C::msm_bigint(&pp.poly_ck.h, &gamma[..]);
+        end_timer!(y_timer);
+
+        let y_hat_timer = start_timer!(|| "Compute Y Hat");
+        let l = instance.c_hat.len();
+        let powers_of_tau = {
+            let mut acc = vec![];
+            let mut curr = instance.tau;
+            for _ in 0..l {
+                acc.push(curr.into_bigint());
+                curr *= instance.tau;
+            }
+            acc
+        };
+        let y_hat = proof.commitment.a_hat
+            + C::msm_bigint(&instance.c_hat, &powers_of_tau[..]) * challenge
+            - C::msm_bigint(&pp.coeff_ck.h, &gamma[..]);
+        drop(powers_of_tau);
+        drop(gamma);
+        end_timer!(y_hat_timer);
+        end_timer!(expected_timer);
+        end_timer!(prepare_timer);
+
+        Ok((
+            RecursionPublicParameters { g, g_hat },
+            RecursionInstance {
+                y: y.into_affine(),
+                y_hat: y_hat.into_affine(),
+            },
+            RecursionWitness { z: proof.z.clone() },
+        ))
+    }
+}
@@ -0,0 +1,116 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+mod data_structure;
+pub use data_structure::*;
+
+mod generator;
+mod prover;
+mod verifier;
+
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_eq::AmEq, Error},
+};
+
+/// The three moves are those of `AmEq`; only `prove` compresses the response.
+pub struct CompAmEq<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+impl<C: CurveGroup> SigmaProtocol for CompAmEq<C> {
+    type PublicParameters = PublicParameters<C>;
+    type Instance = Instance<C>;
+    type Witness = Witness<C>;
+    type Proof = Proof<C>;
+
+    type Statement = <AmEq<C> as SigmaProtocol>::Statement;
+    type Commitment = <AmEq<C> as SigmaProtocol>::Commitment;
+    type State = <AmEq<C> as SigmaProtocol>::State;
+    type Challenge = C::ScalarField;
+    type Response = <AmEq<C> as SigmaProtocol>::Response;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
+        Ok(pp.clone())
+    }
+
+    fn prove<T: TranscriptProtocol, R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        witness: &Self::Witness,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        Self::create_proof_with_combined(pp, instance, witness, transcript, rng)
+    }
+
+    fn verify<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> Result<bool, Self::Error> {
+        Self::verify_proof(pp, instance, proof, transcript)
+    }
+
+    fn bind<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        AmEq::bind(pp, instance, transcript)
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        AmEq::commit(pp, statement, witness, rng)
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        AmEq::<C>::absorb(commitment, transcript)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        AmEq::<C>::challenge(transcript)
+    }
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        AmEq::respond(pp, statement, witness, state, challenge)
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        AmEq::check(pp, statement, commitment, challenge, response)
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        AmEq::simulate(pp, statement, challenge, rng)
+    }
+}
@@ -0,0 +1,43 @@
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_eq::AmEq, comp_dl_eq::CompDLEq, Error},
+};
+
+use super::{data_structure::*, CompAmEq};
+
+impl<C: CurveGroup> CompAmEq<C> {
+    pub fn create_proof_with_combined<R: RngCore + CryptoRng>(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let prover_timer = start_timer!(|| "CompAmEq::Prover");
+
+        let statement = AmEq::bind(pp, instance, transcript)?;
+        let (state, (c, commitment)) = AmEq::commit(pp, &statement, witness, rng)?;
+        AmEq::absorb(&(c, commitment.clone()), transcript)?;
+        let challenge = AmEq::<C>::challenge(transcript);
+        let response = AmEq::respond(pp, &statement, witness, state, &challenge)?;
+        let ae_proof = AEProof::from_conversation((c, commitment.clone()), response);
+
+        let (pp, instance, witness) =
+            Self::prepare_for_comp_dl_eq(pp, instance, &ae_proof, challenge)?;
+        let cde_proof = CompDLEq::create_proof(&pp, &instance, &witness, transcript)?;
+
+        end_timer!(prover_timer);
+        Ok(Proof {
+            commitments: cde_proof.commitments,
+            ae: AEProof {
+                c,
+                commitment,
+                z: cde_proof.z,
+                gamma: ae_proof.gamma,
+            },
+        })
+    }
+}
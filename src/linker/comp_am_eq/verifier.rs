@@ -0,0 +1,45 @@
+use ark_ec::CurveGroup;
+
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{am_eq::AmEq, comp_dl_eq::CompDLEq, Error},
+};
+
+use super::{CDEProof, CompAmEq, Instance, Proof, PublicParameters};
+
+impl<C: CurveGroup> CompAmEq<C> {
+    pub fn verify_proof<T: TranscriptProtocol>(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+        transcript: &mut T,
+    ) -> Result<bool, Error> {
+        if pp.coeff_ck.h.len() != pp.poly_ck.h.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.poly_ck.h.len(),
+                found: pp.coeff_ck.h.len(),
+            });
+        }
+        if proof.ae.z.len() != 2 || proof.ae.gamma.len() != pp.poly_ck.h.len() {
+            return Err(Error::MalformedProof);
+        }
+
+        let verifier_timer = start_timer!(|| "CompAmEq::Verify");
+
+        AmEq::bind(pp, instance, transcript)?;
+        AmEq::absorb(&(proof.ae.c, proof.ae.commitment.clone()), transcript)?;
+        let challenge = AmEq::<C>::challenge(transcript);
+
+        let (pp, instance, witness) =
+            Self::prepare_for_comp_dl_eq(pp, instance, &proof.ae, challenge)?;
+
+        let cde_proof = CDEProof {
+            commitments: proof.commitments.clone(),
+            z: witness.z.clone(),
+        };
+        let proof = CompDLEq::verify_proof(&pp, &instance, &cde_proof, transcript)?;
+
+        end_timer!(verifier_timer);
+        Ok(proof)
+    }
+}
@@ -1,7 +1,10 @@
 use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
-use crate::solidity::Solidity;
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
 
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct PublicParameters<C: CurveGroup> {
@@ -18,6 +21,12 @@ pub struct Instance<C: CurveGroup> {
     pub y_hat: C::Affine,
 }
 
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&[self.y, self.y_hat], dest)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<C: CurveGroup> {
     pub z: Vec<C::ScalarField>,
@@ -46,8 +55,104 @@ where
     }
 }
 
+/// Laid out as its `MultiCommitment`
+impl<C: CurveGroup> AppendToTranscript for Commitment<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        MultiCommitment::from(self).encode(encoding, dest)
+    }
+}
+
+/// The final size of the folding is the length of `z`
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<C: CurveGroup> {
     pub commitments: Vec<Commitment<C>>,
     pub z: Vec<C::ScalarField>,
 }
+
+/// `k` generator vectors of the same length, under which one witness opens `k` instances
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiPublicParameters<C: CurveGroup> {
+    pub bases: Vec<Vec<C::Affine>>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiInstance<C: CurveGroup> {
+    /// `y_i = <bases_i, z>`
+    pub y: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> AppendToTranscript for MultiInstance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&self.y, dest)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiCommitment<C: CurveGroup> {
+    pub left: Vec<C::Affine>,
+    pub right: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> Solidity for MultiCommitment<C>
+where
+    C::Affine: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        self.left
+            .iter()
+            .zip(&self.right)
+            .flat_map(|(l, r)| [l.to_solidity(), r.to_solidity()].concat())
+            .collect()
+    }
+}
+
+/// The pairs `(left_i, right_i)` one after the other
+impl<C: CurveGroup> AppendToTranscript for MultiCommitment<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        self.left
+            .iter()
+            .zip(&self.right)
+            .try_for_each(|(l, r)| encoding.points(&[*l, *r], dest))
+    }
+}
+
+/// The final size of the folding is the length of `z`
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiProof<C: CurveGroup> {
+    pub commitments: Vec<MultiCommitment<C>>,
+    pub z: Vec<C::ScalarField>,
+}
+
+impl<C: CurveGroup> From<&PublicParameters<C>> for MultiPublicParameters<C> {
+    fn from(pp: &PublicParameters<C>) -> Self {
+        Self {
+            bases: vec![pp.g.clone(), pp.g_hat.clone()],
+        }
+    }
+}
+
+impl<C: CurveGroup> From<&Instance<C>> for MultiInstance<C> {
+    fn from(instance: &Instance<C>) -> Self {
+        Self {
+            y: vec![instance.y, instance.y_hat],
+        }
+    }
+}
+
+impl<C: CurveGroup> From<&Commitment<C>> for MultiCommitment<C> {
+    fn from(commitment: &Commitment<C>) -> Self {
+        Self {
+            left: vec![commitment.left, commitment.left_hat],
+            right: vec![commitment.right, commitment.right_hat],
+        }
+    }
+}
+
+impl<C: CurveGroup> From<&Proof<C>> for MultiProof<C> {
+    fn from(proof: &Proof<C>) -> Self {
+        Self {
+            commitments: proof.commitments.iter().map(Into::into).collect(),
+            z: proof.z.clone(),
+        }
+    }
+}
@@ -1,89 +1,107 @@
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_std::Zero;
+use ark_ec::CurveGroup;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use crate::linker::Error;
+
 use super::{data_structure::*, CompDLEq};
 
 impl<C: CurveGroup> CompDLEq<C> {
     /// Prepare public parameters for the protocol.
     /// if g and g_hat are not of the same length, return an error.
-    /// Otherwise, return the public parameters with an extended power-of-two length for g and g_hat.
-    pub fn prepare_public_parameters(pp: &PublicParameters<C>) -> Result<PublicParameters<C>, ()> {
-        if pp.g.len() != pp.g_hat.len() {
-            return Err(());
-        }
-
-        let diff = Self::rescale_size(pp.g.len()) - pp.g.len();
-
-        Ok(PublicParameters {
-            g: vec![pp.g.clone(), vec![C::Affine::generator(); diff]].concat(),
-            g_hat: vec![pp.g_hat.clone(), vec![C::Affine::generator(); diff]].concat(),
-        })
-    }
-
-    pub fn prepare_witness(witness: &Witness<C>) -> Result<Witness<C>, ()> {
-        let diff = Self::rescale_size(witness.z.len()) - witness.z.len();
+    /// Any length is supported, so the generators are used as they are.
+    pub fn prepare_public_parameters(
+        pp: &PublicParameters<C>,
+    ) -> Result<PublicParameters<C>, Error> {
+        Self::check_bases(&[pp.g.clone(), pp.g_hat.clone()])?;
 
-        Ok(Witness {
-            z: vec![witness.z.clone(), vec![C::ScalarField::zero(); diff]].concat(),
-        })
+        Ok(pp.clone())
     }
 
-    /// Both the prover and verifier need to update the public parameters
+    /// Both the prover and verifier need to update the public parameters.
+    /// On an odd length, the last generator has no pair and is carried forward as `e g_c`.
     pub fn update_public_parameters(
-        pp: &PublicParameters<C>,
+        pp: &MultiPublicParameters<C>,
         challenge: C::ScalarField,
-    ) -> Result<PublicParameters<C>, ()> {
+    ) -> Result<MultiPublicParameters<C>, Error> {
         let update_timer = start_timer!(|| "CompDLEq::Update Public Parameters");
 
-        let mid = pp.g.len() / 2;
-
-        let g_timer = start_timer!(|| "Update G");
-        let g = cfg_iter!(pp.g[..mid])
-            .zip(&pp.g[mid..])
-            .map(|(l, r)| *l * challenge + r)
-            .collect::<Vec<_>>();
-        end_timer!(g_timer);
-
-        let g_hat_timer = start_timer!(|| "Update G Hat");
-        let g_hat = cfg_iter!(pp.g_hat[..mid])
-            .zip(&pp.g_hat[mid..])
-            .map(|(l, r)| *l * challenge + r)
-            .collect::<Vec<_>>();
-        end_timer!(g_hat_timer);
+        let bases = pp
+            .bases
+            .iter()
+            .map(|g| {
+                let mid = g.len() / 2;
+                let mut folded = cfg_iter!(g[..mid])
+                    .zip(&g[mid..2 * mid])
+                    .map(|(l, r)| *l * challenge + r)
+                    .collect::<Vec<_>>();
+                folded.extend(g[2 * mid..].iter().map(|c| *c * challenge));
+                C::normalize_batch(&folded)
+            })
+            .collect();
         end_timer!(update_timer);
 
-        Ok(PublicParameters {
-            g: C::normalize_batch(&g),
-            g_hat: C::normalize_batch(&g_hat),
-        })
+        Ok(MultiPublicParameters { bases })
     }
 
     pub fn update_instance(
-        instance: &Instance<C>,
-        commitment: &Commitment<C>,
+        instance: &MultiInstance<C>,
+        commitment: &MultiCommitment<C>,
         challenge: C::ScalarField,
-    ) -> Result<Instance<C>, ()> {
+    ) -> Result<MultiInstance<C>, Error> {
+        if commitment.left.len() != instance.y.len() || commitment.right.len() != instance.y.len() {
+            return Err(Error::MalformedProof);
+        }
         let update_timer = start_timer!(|| "CompDLEq::Update Instance");
         let sqr_challenge = challenge * challenge;
-        let y = commitment.left + instance.y * challenge + commitment.right * sqr_challenge;
-        let y_hat =
-            commitment.left_hat + instance.y_hat * challenge + commitment.right_hat * sqr_challenge;
+        let y = instance
+            .y
+            .iter()
+            .zip(commitment.left.iter().zip(&commitment.right))
+            .map(|(y, (left, right))| *left + *y * challenge + *right * sqr_challenge)
+            .collect::<Vec<_>>();
         end_timer!(update_timer);
 
-        Ok(Instance {
-            y: y.into_affine(),
-            y_hat: y_hat.into_affine(),
+        Ok(MultiInstance {
+            y: C::normalize_batch(&y),
         })
     }
 
-    fn rescale_size(l: usize) -> usize {
-        if l.is_power_of_two() {
-            l
-        } else {
-            l.next_power_of_two()
+    /// The generator vectors must be of the same length, which is returned
+    pub fn check_bases(bases: &[Vec<C::Affine>]) -> Result<usize, Error> {
+        let n = bases.first().map(|g| g.len()).unwrap_or_default();
+        match bases.iter().find(|g| g.len() != n) {
+            Some(g) => Err(Error::LengthMismatch {
+                expected: n,
+                found: g.len(),
+            }),
+            None => Ok(n),
+        }
+    }
+
+    /// Size of the vectors after one round of folding
+    pub fn folded_size(n: usize) -> usize {
+        n - n / 2
+    }
+
+    /// Size at which the folding of `n` elements stops for the given `threshold`
+    pub fn final_size(n: usize, threshold: usize) -> usize {
+        let mut size = n;
+        while size > threshold.max(1) {
+            size = Self::folded_size(size);
+        }
+        size
+    }
+
+    /// Number of rounds folding `n` elements down to `final_size`,
+    /// or `None` if the folding never stops at `final_size`
+    pub fn folding_rounds(n: usize, final_size: usize) -> Option<usize> {
+        let (mut size, mut rounds) = (n, 0);
+        while size > final_size.max(1) {
+            size = Self::folded_size(size);
+            rounds += 1;
         }
+        (size == final_size).then_some(rounds)
     }
 }
@@ -0,0 +1,158 @@
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+use crate::{
+    crypto::protocol::channel::{Channel, Party, Status},
+    linker::Error,
+};
+
+use super::{CompDLEq, MultiCommitment, MultiInstance, MultiPublicParameters, Witness};
+
+enum ProverState {
+    Start,
+    /// A commitment was sent, waiting for its challenge
+    Folding,
+    Done,
+}
+
+/// Prover of the folding of `CompDLEq` over a channel.
+/// It moves first, sending one commitment per round and the final `z` once at most
+/// `threshold` elements are left.
+pub struct InteractiveProver<C: CurveGroup> {
+    pp: MultiPublicParameters<C>,
+    instance: MultiInstance<C>,
+    witness: Witness<C>,
+    threshold: usize,
+    state: ProverState,
+}
+
+impl<C: CurveGroup> InteractiveProver<C> {
+    pub fn new(
+        pp: MultiPublicParameters<C>,
+        instance: MultiInstance<C>,
+        witness: Witness<C>,
+        threshold: usize,
+    ) -> Result<Self, Error> {
+        let n = CompDLEq::<C>::check_bases(&pp.bases)?;
+        if witness.z.len() != n {
+            return Err(Error::LengthMismatch {
+                expected: n,
+                found: witness.z.len(),
+            });
+        }
+
+        Ok(Self {
+            pp,
+            instance,
+            witness,
+            threshold,
+            state: ProverState::Start,
+        })
+    }
+}
+
+impl<C: CurveGroup> Party for InteractiveProver<C> {
+    type Output = ();
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<()>, Error> {
+        match self.state {
+            ProverState::Start => channel.bind(b"instance", &self.instance)?,
+            ProverState::Folding => {
+                let challenge = channel.receive_challenge(b"challenge")?;
+                self.pp = CompDLEq::update_public_parameters(&self.pp, challenge)?;
+                self.witness = CompDLEq::update_witness(&self.witness, challenge)?;
+            }
+            ProverState::Done => return Ok(Status::Done(())),
+        }
+
+        if self.witness.z.len() > self.threshold.max(1) {
+            let commitment = CompDLEq::compute_depth_commitment_from_updated_parameters(
+                &self.pp,
+                &self.witness,
+            )?;
+            channel.send(b"commitments", &commitment)?;
+            self.state = ProverState::Folding;
+            Ok(Status::Continue)
+        } else {
+            channel.send(b"z", &self.witness.z)?;
+            self.state = ProverState::Done;
+            Ok(Status::Done(()))
+        }
+    }
+}
+
+/// Verifier of the folding of `CompDLEq` over a channel, drawing the challenges with `rng`.
+/// Both parties agree on `threshold`, hence on the number of rounds.
+pub struct InteractiveVerifier<C: CurveGroup, R> {
+    pp: MultiPublicParameters<C>,
+    instance: MultiInstance<C>,
+    rng: R,
+    final_size: usize,
+    rounds: usize,
+    challenges: Option<Vec<C::ScalarField>>,
+    accepted: Option<bool>,
+}
+
+impl<C: CurveGroup, R> InteractiveVerifier<C, R> {
+    pub fn new(
+        pp: MultiPublicParameters<C>,
+        instance: MultiInstance<C>,
+        threshold: usize,
+        rng: R,
+    ) -> Result<Self, Error> {
+        let n = CompDLEq::<C>::check_bases(&pp.bases)?;
+        if instance.y.len() != pp.bases.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.bases.len(),
+                found: instance.y.len(),
+            });
+        }
+        let final_size = CompDLEq::<C>::final_size(n, threshold);
+        let rounds = CompDLEq::<C>::folding_rounds(n, final_size).unwrap_or_default();
+
+        Ok(Self {
+            pp,
+            instance,
+            rng,
+            final_size,
+            rounds,
+            challenges: None,
+            accepted: None,
+        })
+    }
+}
+
+impl<C: CurveGroup, R: RngCore + CryptoRng> Party for InteractiveVerifier<C, R> {
+    type Output = bool;
+    type Error = Error;
+
+    fn step<Ch: Channel>(&mut self, channel: &mut Ch) -> Result<Status<bool>, Error> {
+        if let Some(accepted) = self.accepted {
+            return Ok(Status::Done(accepted));
+        }
+        let challenges = match self.challenges.as_mut() {
+            Some(challenges) => challenges,
+            None => {
+                channel.bind(b"instance", &self.instance)?;
+                self.challenges.insert(vec![])
+            }
+        };
+
+        if challenges.len() < self.rounds {
+            let commitment: MultiCommitment<C> = channel.receive(b"commitments")?;
+            let challenge = channel.send_challenge(b"challenge", &mut self.rng)?;
+            self.instance = CompDLEq::update_instance(&self.instance, &commitment, challenge)?;
+            challenges.push(challenge);
+            Ok(Status::Continue)
+        } else {
+            let z: Vec<C::ScalarField> = channel.receive(b"z")?;
+            if z.len() != self.final_size {
+                return Err(Error::MalformedProof);
+            }
+            let accepted = CompDLEq::check_folding(&self.pp, &self.instance, challenges, &z)?;
+            self.accepted = Some(accepted);
+            Ok(Status::Done(accepted))
+        }
+    }
+}
@@ -1,17 +1,30 @@
 use std::marker::PhantomData;
 
 use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
 use ark_std::rand::{CryptoRng, RngCore};
 
 mod data_structure;
 pub use data_structure::*;
 
 mod generator;
+mod interactive;
+pub use interactive::{InteractiveProver, InteractiveVerifier};
+
 mod prover;
+mod simulator;
 mod verifier;
 
-use crate::crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol};
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, TranscriptProtocol},
+    },
+    linker::Error,
+};
 
+/// `prove` and `verify` run the compressed protocol, while the three moves are those of
+/// the uncompressed one, with the response `s = r + e z` sent in full.
 pub struct CompDLEq<C: CurveGroup> {
     _group: PhantomData<C>,
 }
@@ -21,9 +34,17 @@ impl<C: CurveGroup> SigmaProtocol for CompDLEq<C> {
     type Instance = Instance<C>;
     type Witness = Witness<C>;
     type Proof = Proof<C>;
-    type Error = ();
 
-    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, ()> {
+    type Statement = Instance<C>;
+    /// `(<g, r>, <g_hat, r>)` for the random vector `r`
+    type Commitment = Instance<C>;
+    type State = Witness<C>;
+    type Challenge = C::ScalarField;
+    type Response = Witness<C>;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
         Self::prepare_public_parameters(pp)
     }
 
@@ -33,7 +54,7 @@ impl<C: CurveGroup> SigmaProtocol for CompDLEq<C> {
         witness: &Self::Witness,
         transcript: &mut T,
         _: &mut R,
-    ) -> Result<Self::Proof, ()> {
+    ) -> Result<Self::Proof, Self::Error> {
         Self::create_proof(pp, instance, witness, transcript)
     }
 
@@ -42,7 +63,95 @@ impl<C: CurveGroup> SigmaProtocol for CompDLEq<C> {
         instance: &Self::Instance,
         proof: &Self::Proof,
         transcript: &mut T,
-    ) -> Result<bool, ()> {
+    ) -> Result<bool, Self::Error> {
         Self::verify_proof(pp, instance, proof, transcript)
     }
+
+    fn bind<T: TranscriptProtocol>(
+        _pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        instance.append_to_transcript(b"instance", transcript)?;
+        Ok(instance.clone())
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        let r = Witness {
+            z: (0..witness.z.len())
+                .map(|_| C::ScalarField::rand(rng))
+                .collect(),
+        };
+        let commitment = Self::evaluate(pp, &r.z)?;
+
+        Ok((r, commitment))
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        Ok(commitment.append_to_transcript(b"commitment", transcript)?)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        transcript.challenge_scalar(b"challenge")
+    }
+
+    fn respond(
+        _pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        if witness.z.len() != state.z.len() {
+            return Err(Error::LengthMismatch {
+                expected: state.z.len(),
+                found: witness.z.len(),
+            });
+        }
+
+        Ok(Witness {
+            z: state
+                .z
+                .iter()
+                .zip(&witness.z)
+                .map(|(r, z)| *r + *challenge * z)
+                .collect(),
+        })
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        if response.z.len() != pp.g.len() {
+            return Err(Error::MalformedProof);
+        }
+        let evaluation = Self::evaluate(pp, &response.z)?;
+
+        Ok(
+            evaluation.y == (commitment.y + statement.y * challenge).into_affine()
+                && evaluation.y_hat
+                    == (commitment.y_hat + statement.y_hat * challenge).into_affine(),
+        )
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        Self::simulate_conversation(pp, statement, *challenge, rng)
+    }
 }
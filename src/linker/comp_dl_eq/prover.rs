@@ -4,19 +4,24 @@ use ark_ff::PrimeField;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{crypto::protocol::transcript::TranscriptProtocol, linker::Error};
 
 use super::{data_structure::*, CompDLEq};
 
 impl<C: CurveGroup> CompDLEq<C> {
     pub fn compute_depth_commitment_from_updated_parameters(
-        pp: &PublicParameters<C>,
+        pp: &MultiPublicParameters<C>,
         witness: &Witness<C>,
-    ) -> Result<Commitment<C>, ()> {
-        if witness.z.len() != pp.g.len() || witness.z.len() != pp.g_hat.len() {
-            return Err(());
+    ) -> Result<MultiCommitment<C>, Error> {
+        let n = Self::check_bases(&pp.bases)?;
+        if witness.z.len() != n {
+            return Err(Error::LengthMismatch {
+                expected: n,
+                found: witness.z.len(),
+            });
         }
         let commit_timer = start_timer!(|| "CompDLEq::Depth Commit");
+        // an unpaired last element is carried forward and adds no cross term
         let mid = witness.z.len() / 2;
 
         let z_bigint = cfg_iter!(witness.z)
@@ -24,38 +29,35 @@ impl<C: CurveGroup> CompDLEq<C> {
             .collect::<Vec<_>>();
 
         let lr_timer = start_timer!(|| "Compute LR");
-        let left = C::msm_bigint(&pp.g[mid..], &z_bigint[..mid]);
-        let right = C::msm_bigint(&pp.g[..mid], &z_bigint[mid..]);
-
+        let left = cfg_iter!(pp.bases)
+            .map(|g| C::msm_bigint(&g[mid..2 * mid], &z_bigint[..mid]))
+            .collect::<Vec<_>>();
+        let right = cfg_iter!(pp.bases)
+            .map(|g| C::msm_bigint(&g[..mid], &z_bigint[mid..2 * mid]))
+            .collect::<Vec<_>>();
         end_timer!(lr_timer);
-
-        let lr_hat_timer = start_timer!(|| "Compute LR Hat");
-        let left_hat = C::msm_bigint(&pp.g_hat[mid..], &z_bigint[..mid]);
-        let right_hat = C::msm_bigint(&pp.g_hat[..mid], &z_bigint[mid..]);
-
-        end_timer!(lr_hat_timer);
         end_timer!(commit_timer);
 
-        Ok(Commitment {
-            left: left.into_affine(),
-            right: right.into_affine(),
-            left_hat: left_hat.into_affine(),
-            right_hat: right_hat.into_affine(),
+        Ok(MultiCommitment {
+            left: C::normalize_batch(&left),
+            right: C::normalize_batch(&right),
         })
     }
 
     /// Update the witness with the challenge.
     /// Prover also needs to update the witness with the challenge.
+    /// On an odd length, the last element is carried forward as it is.
     pub fn update_witness(
         witness: &Witness<C>,
         challenge: C::ScalarField,
-    ) -> Result<Witness<C>, ()> {
+    ) -> Result<Witness<C>, Error> {
         let witness_timer = start_timer!(|| "Update witness");
         let mid = witness.z.len() / 2;
-        let z = cfg_iter!(witness.z[..mid])
-            .zip(&witness.z[mid..])
+        let mut z = cfg_iter!(witness.z[..mid])
+            .zip(&witness.z[mid..2 * mid])
             .map(|(l, r)| *l + challenge * r)
             .collect::<Vec<_>>();
+        z.extend_from_slice(&witness.z[2 * mid..]);
         end_timer!(witness_timer);
 
         Ok(Witness { z })
@@ -66,10 +68,57 @@ impl<C: CurveGroup> CompDLEq<C> {
         instance: &Instance<C>,
         witness: &Witness<C>,
         transcript: &mut impl TranscriptProtocol,
-    ) -> Result<Proof<C>, ()> {
-        let threshold = 2;
-        if pp.g.len() != pp.g_hat.len() {
-            return Err(());
+    ) -> Result<Proof<C>, Error> {
+        Self::create_proof_with_threshold(pp, instance, witness, 2, transcript)
+    }
+
+    /// Create a proof whose folding stops once at most `threshold` elements are left
+    pub fn create_proof_with_threshold(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        threshold: usize,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Proof<C>, Error> {
+        let proof =
+            Self::create_multi_proof(&pp.into(), &instance.into(), witness, threshold, transcript)?;
+
+        Ok(Proof {
+            commitments: proof
+                .commitments
+                .iter()
+                .map(|commitment| Commitment {
+                    left: commitment.left[0],
+                    right: commitment.right[0],
+                    left_hat: commitment.left[1],
+                    right_hat: commitment.right[1],
+                })
+                .collect(),
+            z: proof.z,
+        })
+    }
+
+    /// Prove that one witness opens every instance under its generator vector,
+    /// halving the generators until at most `threshold` elements are left
+    pub fn create_multi_proof(
+        pp: &MultiPublicParameters<C>,
+        instance: &MultiInstance<C>,
+        witness: &Witness<C>,
+        threshold: usize,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<MultiProof<C>, Error> {
+        let n = Self::check_bases(&pp.bases)?;
+        if witness.z.len() != n {
+            return Err(Error::LengthMismatch {
+                expected: n,
+                found: witness.z.len(),
+            });
+        }
+        if instance.y.len() != pp.bases.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.bases.len(),
+                found: instance.y.len(),
+            });
         }
 
         let prover_timer = start_timer!(|| "CompDLEq::Prover");
@@ -77,11 +126,11 @@ impl<C: CurveGroup> CompDLEq<C> {
 
         let mut pp = pp.clone();
         let mut instance = instance.clone();
-        let mut witness = Self::prepare_witness(witness)?;
-        while pp.g.len() > threshold {
+        let mut witness = witness.clone();
+        while witness.z.len() > threshold.max(1) {
             let commitment = Self::compute_depth_commitment_from_updated_parameters(&pp, &witness)?;
 
-            let challenge = Self::compute_challenge(&commitment, transcript);
+            let challenge = Self::compute_challenge(&commitment, transcript)?;
 
             pp = Self::update_public_parameters(&pp, challenge)?;
             instance = Self::update_instance(&instance, &commitment, challenge)?;
@@ -91,7 +140,7 @@ impl<C: CurveGroup> CompDLEq<C> {
 
         end_timer!(prover_timer);
 
-        Ok(Proof {
+        Ok(MultiProof {
             commitments,
             z: witness.z,
         })
@@ -0,0 +1,31 @@
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::linker::Error;
+
+use super::{CompDLEq, Instance, PublicParameters, Witness};
+
+impl<C: CurveGroup> CompDLEq<C> {
+    /// Simulate an accepting conversation of the uncompressed protocol for the challenge,
+    /// by sampling the response `s` and setting the commitment to `(<g, s>, <g_hat, s>) - e Y`
+    pub fn simulate_conversation(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        challenge: C::ScalarField,
+        rng: &mut impl Rng,
+    ) -> Result<(Instance<C>, Witness<C>), Error> {
+        let simulator_timer = start_timer!(|| "CompDLEq::Simulator");
+        let response = Witness {
+            z: (0..pp.g.len()).map(|_| C::ScalarField::rand(rng)).collect(),
+        };
+        let evaluation = Self::evaluate(pp, &response.z)?;
+        let commitment = Instance {
+            y: (evaluation.y.into_group() - instance.y * challenge).into_affine(),
+            y_hat: (evaluation.y_hat.into_group() - instance.y_hat * challenge).into_affine(),
+        };
+        end_timer!(simulator_timer);
+
+        Ok((commitment, response))
+    }
+}
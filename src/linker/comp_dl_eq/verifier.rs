@@ -1,14 +1,19 @@
-use ark_ec::{AffineRepr, CurveGroup};
+use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
-use ark_std::{vec::Vec, One, Zero};
+use ark_std::{vec::Vec, One};
 
-use crate::crypto::protocol::transcript::TranscriptProtocol;
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, TranscriptProtocol},
+    linker::Error,
+};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use super::{Commitment, CompDLEq, Instance, Proof, PublicParameters};
+use super::{
+    CompDLEq, Instance, MultiCommitment, MultiInstance, MultiProof, MultiPublicParameters, Proof,
+    PublicParameters,
+};
 
 impl<C: CurveGroup> CompDLEq<C> {
     pub fn verify_proof(
@@ -16,120 +21,148 @@ impl<C: CurveGroup> CompDLEq<C> {
         instance: &Instance<C>,
         proof: &Proof<C>,
         transcript: &mut impl TranscriptProtocol,
-    ) -> Result<bool, ()> {
-        let threshold = 2;
-        if proof.z.len() != threshold || pp.g.len() != pp.g_hat.len() {
-            return Err(());
+    ) -> Result<bool, Error> {
+        Self::verify_multi_proof(&pp.into(), &instance.into(), &proof.into(), transcript)
+    }
+
+    /// The final size of the folding is read from the proof
+    pub fn verify_multi_proof(
+        pp: &MultiPublicParameters<C>,
+        instance: &MultiInstance<C>,
+        proof: &MultiProof<C>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        let n = Self::check_bases(&pp.bases)?;
+        if instance.y.len() != pp.bases.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.bases.len(),
+                found: instance.y.len(),
+            });
+        }
+        // one round per halving of the generators down to the final size
+        if Self::folding_rounds(n, proof.z.len()) != Some(proof.commitments.len()) {
+            return Err(Error::MalformedProof);
         }
 
         let verifier_timer = start_timer!(|| "CompDLEq::Verifier");
-        let mut pp = pp.clone();
         let mut instance = instance.clone();
         let mut challenges = vec![];
-        proof.commitments.iter().for_each(|commitment| {
-            let challenge = Self::compute_challenge(commitment, transcript);
+        for commitment in proof.commitments.iter() {
+            let challenge = Self::compute_challenge(commitment, transcript)?;
             challenges.push(challenge);
 
-            instance = Self::update_instance(&instance, commitment, challenge).unwrap();
-        });
-        pp = Self::update_public_parameters_once(&pp, &challenges).unwrap();
-
-        if proof.z.len() != pp.g.len() {
-            return Err(());
+            instance = Self::update_instance(&instance, commitment, challenge)?;
         }
-        let y_real = (pp.g[0] * proof.z[0] + pp.g[1] * proof.z[1]).into_affine();
-        let y_hat_real = (pp.g_hat[0] * proof.z[0] + pp.g_hat[1] * proof.z[1]).into_affine();
+        let valid = Self::check_folding(pp, &instance, &challenges, &proof.z)?;
         end_timer!(verifier_timer);
 
-        Ok(instance.y == y_real && instance.y_hat == y_hat_real)
+        Ok(valid)
     }
 
-    /// Verifier could update the public parameters faster
+    /// Check the final `z` against the folded instance, folding the generators at once
+    pub fn check_folding(
+        pp: &MultiPublicParameters<C>,
+        instance: &MultiInstance<C>,
+        challenges: &[C::ScalarField],
+        z: &[C::ScalarField],
+    ) -> Result<bool, Error> {
+        let pp = Self::update_public_parameters_once(pp, challenges)?;
+
+        let z = cfg_iter!(z).map(|z| z.into_bigint()).collect::<Vec<_>>();
+        let y_real = cfg_iter!(pp.bases)
+            .map(|g| C::msm_bigint(g, &z))
+            .collect::<Vec<_>>();
+
+        Ok(instance.y == C::normalize_batch(&y_real))
+    }
+
+    /// Verifier could update the public parameters faster.
+    /// Every generator ends up in one position of the folded vector, scaled by a product
+    /// of challenges, so that each folded generator is a single MSM.
     pub fn update_public_parameters_once(
-        pp: &PublicParameters<C>,
+        pp: &MultiPublicParameters<C>,
         challenges: &[C::ScalarField],
-    ) -> Result<PublicParameters<C>, ()> {
+    ) -> Result<MultiPublicParameters<C>, Error> {
+        let n = Self::check_bases(&pp.bases)?;
         let update_timer = start_timer!(|| "CompDLEq::Update Public Parameters");
 
-        let challenges = {
-            let n = 1 << challenges.len();
-            let mut arr = cfg_into_iter!(0..n)
-                .map(|mut b| {
-                    let mut k = C::ScalarField::one();
-                    let mut i = challenges.len() - 1;
-                    while b > 0 {
-                        if (b & 1) > 0 {
-                            k *= challenges[i];
-                        }
-                        b >>= 1;
-                        i -= 1;
-                    }
-                    k.into_bigint()
-                })
-                .collect::<Vec<_>>();
-            arr.reverse();
-            arr
-        };
-
-        let g = Self::fold(&pp.g, &challenges);
-        let g_hat = Self::fold(&pp.g_hat, &challenges);
+        let mut size = n;
+        let mut positions = (0..n).collect::<Vec<_>>();
+        let mut factors = vec![C::ScalarField::one(); n];
+        for challenge in challenges {
+            let mid = size / 2;
+            // `e g_l + g_r`, and the unpaired last generator becomes `e g_c`
+            for (position, factor) in positions.iter_mut().zip(factors.iter_mut()) {
+                if *position < mid {
+                    *factor *= challenge;
+                } else if *position < 2 * mid {
+                    *position -= mid;
+                } else {
+                    *position = mid;
+                    *factor *= challenge;
+                }
+            }
+            size = Self::folded_size(size);
+        }
+
+        let mut columns = vec![vec![]; size];
+        for (i, position) in positions.into_iter().enumerate() {
+            columns[position].push(i);
+        }
+        let factors = cfg_iter!(factors)
+            .map(|f| f.into_bigint())
+            .collect::<Vec<_>>();
+
+        let bases = pp
+            .bases
+            .iter()
+            .map(|g| Self::fold(g, &columns, &factors))
+            .collect();
         end_timer!(update_timer);
 
-        Ok(PublicParameters { g, g_hat })
+        Ok(MultiPublicParameters { bases })
     }
 
+    /// The j-th folded generator collects the generators in the j-th column
     fn fold(
         generators: &[C::Affine],
+        columns: &[Vec<usize>],
         factors: &[<C::ScalarField as PrimeField>::BigInt],
     ) -> Vec<C::Affine> {
-        if (generators.len() != factors.len() << 1) {
-            println!("error");
-        }
-        assert_eq!(
-            generators.len(),
-            factors.len() << 1,
-            "Fold: length mismatch"
-        );
-        let left = cfg_iter!(generators)
-            .step_by(2)
-            .map(|l| l.clone())
-            .collect::<Vec<_>>();
-        let right = cfg_iter!(generators)
-            .skip(1)
-            .step_by(2)
-            .map(|r| r.clone())
+        let g = cfg_iter!(columns)
+            .map(|column| {
+                let bases = column.iter().map(|i| generators[*i]).collect::<Vec<_>>();
+                let scalars = column.iter().map(|i| factors[*i]).collect::<Vec<_>>();
+                C::msm_bigint(&bases, &scalars)
+            })
             .collect::<Vec<_>>();
-        let g = vec![
-            C::msm_bigint(&left, &factors[..]),
-            C::msm_bigint(&right, &factors[..]),
-        ];
         C::normalize_batch(&g)
     }
 
     pub fn compute_challenge(
-        commitment: &Commitment<C>,
+        commitment: &MultiCommitment<C>,
         transcript: &mut impl TranscriptProtocol,
-    ) -> C::ScalarField {
-        let commitment = vec![
-            commitment.left,
-            commitment.right,
-            commitment.left_hat,
-            commitment.right_hat,
-        ];
-
-        let bytes = cfg_iter!(commitment)
-            .map(|p| {
-                let mut _bytes = vec![];
-                let (x, y) = p.xy().unwrap();
-                y.serialize_uncompressed(&mut _bytes).unwrap();
-                x.serialize_uncompressed(&mut _bytes).unwrap();
-                _bytes.reverse();
-                _bytes
-            })
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Result<C::ScalarField, Error> {
+        commitment.append_to_transcript(b"commitments", transcript)?;
 
-        transcript.append(b"commitments", &bytes[..]);
-        transcript.challenge_scalar(b"challenge")
+        Ok(transcript.challenge_scalar(b"challenge"))
+    }
+
+    /// `(<g, z>, <g_hat, z>)`, the instance the witness `z` satisfies
+    pub fn evaluate(pp: &PublicParameters<C>, z: &[C::ScalarField]) -> Result<Instance<C>, Error> {
+        if z.len() != pp.g.len() || z.len() != pp.g_hat.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.g.len(),
+                found: z.len(),
+            });
+        }
+        let z = cfg_iter!(z).map(|s| s.into_bigint()).collect::<Vec<_>>();
+        let y = C::msm_bigint(&pp.g, &z[..]);
+        let y_hat = C::msm_bigint(&pp.g_hat, &z[..]);
+
+        Ok(Instance {
+            y: y.into_affine(),
+            y_hat: y_hat.into_affine(),
+        })
     }
 }
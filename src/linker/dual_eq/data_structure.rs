@@ -0,0 +1,122 @@
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+pub use crate::linker::am_com_eq::CommittingKey;
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
+
+/// Two committing keys over the same messages, each with blinding generators of its own,
+/// e.g. the proof dependent keys of two circuits with their `gamma_eta_g1`
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PublicParameters<C: CurveGroup> {
+    pub ck1: CommittingKey<C>,
+    pub ck2: CommittingKey<C>,
+}
+
+impl<C: CurveGroup> Solidity for PublicParameters<C>
+where
+    C::Affine: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        let mut v = vec![
+            self.ck1.g.len().to_string(),
+            self.ck1.h.len().to_string(),
+            self.ck2.h.len().to_string(),
+        ];
+        v.extend(self.ck1.to_solidity());
+        v.extend(self.ck2.to_solidity());
+        v
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Instance<C: CurveGroup> {
+    pub d1: C::Affine,
+    pub d2: C::Affine,
+}
+
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&[self.d1, self.d2], dest)
+    }
+}
+
+/// `d1 = <ck1.g, w> + <ck1.h, alpha1>` and `d2 = <ck2.g, w> + <ck2.h, alpha2>`
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Witness<C: CurveGroup> {
+    pub w: Vec<C::ScalarField>,
+    pub alpha1: Vec<C::ScalarField>,
+    pub alpha2: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Randomness<C: CurveGroup> {
+    pub r: Vec<C::ScalarField>,
+    pub s1: Vec<C::ScalarField>,
+    pub s2: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Commitment<C: CurveGroup> {
+    pub a1: C::Affine,
+    pub a2: C::Affine,
+}
+
+impl<C: CurveGroup> AppendToTranscript for Commitment<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&[self.a1, self.a2], dest)
+    }
+}
+
+impl<C: CurveGroup> Solidity for Commitment<C>
+where
+    C::Affine: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [self.a1.to_solidity(), self.a2.to_solidity()].concat()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Response<C: CurveGroup> {
+    pub z: Vec<C::ScalarField>,
+    pub t1: Vec<C::ScalarField>,
+    pub t2: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<C: CurveGroup> {
+    pub commitment: Commitment<C>,
+    pub z: Vec<C::ScalarField>,
+    pub t1: Vec<C::ScalarField>,
+    pub t2: Vec<C::ScalarField>,
+}
+
+impl<C: CurveGroup> Proof<C> {
+    pub fn from_conversation(commitment: Commitment<C>, response: Response<C>) -> Self {
+        Self {
+            commitment,
+            z: response.z,
+            t1: response.t1,
+            t2: response.t2,
+        }
+    }
+}
+
+impl<C: CurveGroup> Solidity for Proof<C>
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [
+            self.z.to_solidity(),
+            self.t1.to_solidity(),
+            self.t2.to_solidity(),
+            self.commitment.to_solidity(),
+        ]
+        .concat()
+    }
+}
@@ -0,0 +1,110 @@
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::rand::{CryptoRng, Rng, RngCore};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::{sigma::prove_with_phases, transcript::TranscriptProtocol},
+    linker::Error,
+};
+
+use super::{Commitment, DualEq, Instance, Proof, PublicParameters, Randomness, Response, Witness};
+
+impl<C: CurveGroup> DualEq<C> {
+    /// Generate a random and compute the commitment
+    /// `a1 = <ck1.g, r> + <ck1.h, s1>` and `a2 = <ck2.g, r> + <ck2.h, s2>`
+    pub fn create_random_commitment(
+        pp: &PublicParameters<C>,
+        rng: &mut impl Rng,
+    ) -> (Randomness<C>, Commitment<C>) {
+        let commit_timer = start_timer!(|| "DualEq::Commit");
+        let mut sample = |n: usize| -> Vec<C::ScalarField> {
+            (0..n)
+                .map(|_| C::ScalarField::rand(rng))
+                .collect::<Vec<_>>()
+        };
+        let randomness = Randomness::<C> {
+            r: sample(pp.ck1.g.len()),
+            s1: sample(pp.ck1.h.len()),
+            s2: sample(pp.ck2.h.len()),
+        };
+
+        let r = cfg_iter!(randomness.r)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+        let s1 = cfg_iter!(randomness.s1)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+        let s2 = cfg_iter!(randomness.s2)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+        let a1 = C::msm_bigint(&pp.ck1.g, &r) + C::msm_bigint(&pp.ck1.h, &s1);
+        let a2 = C::msm_bigint(&pp.ck2.g, &r) + C::msm_bigint(&pp.ck2.h, &s2);
+        end_timer!(commit_timer);
+
+        (
+            randomness,
+            Commitment {
+                a1: a1.into_affine(),
+                a2: a2.into_affine(),
+            },
+        )
+    }
+
+    /// Compute the response `z = r + e w`, `t1 = s1 + e alpha1` and `t2 = s2 + e alpha2`
+    pub fn compute_response(
+        witness: &Witness<C>,
+        randomness: &Randomness<C>,
+        challenge: C::ScalarField,
+    ) -> Response<C> {
+        let respond = |r: &[C::ScalarField], w: &[C::ScalarField]| {
+            cfg_iter!(r)
+                .zip(w)
+                .map(|(r, w)| *r + challenge * w)
+                .collect::<Vec<_>>()
+        };
+
+        Response {
+            z: respond(&randomness.r, &witness.w),
+            t1: respond(&randomness.s1, &witness.alpha1),
+            t2: respond(&randomness.s2, &witness.alpha2),
+        }
+    }
+
+    /// Prove that `d1` and `d2` commit to the same message, absorbing the instance
+    /// before the commitments `a1` and `a2`
+    pub fn create_proof<R: RngCore + CryptoRng>(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let proof_timer = start_timer!(|| "DualEq::Prover");
+
+        let (commitment, response) =
+            prove_with_phases::<Self, _, _>(pp, instance, witness, transcript, rng)?;
+
+        end_timer!(proof_timer);
+
+        Ok(Proof::from_conversation(commitment, response))
+    }
+
+    /// The witness must match the lengths of the committing keys
+    pub fn check_witness(pp: &PublicParameters<C>, witness: &Witness<C>) -> Result<(), Error> {
+        Self::check_parameters(pp)?;
+        for (expected, found) in [
+            (pp.ck1.g.len(), witness.w.len()),
+            (pp.ck1.h.len(), witness.alpha1.len()),
+            (pp.ck2.h.len(), witness.alpha2.len()),
+        ] {
+            if expected != found {
+                return Err(Error::LengthMismatch { expected, found });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::linker::Error;
+
+use super::{Commitment, DualEq, Instance, PublicParameters, Response};
+
+impl<C: CurveGroup> DualEq<C> {
+    /// Simulate an accepting conversation for the challenge without the openings,
+    /// by sampling the response and solving both verification equations for `(a1, a2)`
+    pub fn simulate_conversation(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        challenge: C::ScalarField,
+        rng: &mut impl Rng,
+    ) -> Result<(Commitment<C>, Response<C>), Error> {
+        let mut sample = |n: usize| -> Vec<C::ScalarField> {
+            (0..n)
+                .map(|_| C::ScalarField::rand(rng))
+                .collect::<Vec<_>>()
+        };
+        let response = Response::<C> {
+            z: sample(pp.ck1.g.len()),
+            t1: sample(pp.ck1.h.len()),
+            t2: sample(pp.ck2.h.len()),
+        };
+        Self::check_response_shape(pp, &response)?;
+        let commitment = Self::expected_commitment(pp, instance, &response, challenge);
+
+        Ok((commitment, response))
+    }
+}
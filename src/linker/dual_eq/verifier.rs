@@ -0,0 +1,96 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::Error,
+};
+
+use super::{Commitment, DualEq, Instance, Proof, PublicParameters, Response};
+
+impl<C: CurveGroup> DualEq<C> {
+    pub fn verify_proof(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        let response = Response {
+            z: proof.z.clone(),
+            t1: proof.t1.clone(),
+            t2: proof.t2.clone(),
+        };
+        Self::check_response_shape(pp, &response)?;
+
+        let verifier_timer = start_timer!(|| "DualEq::Verifier");
+
+        let statement = Self::bind(pp, instance, transcript)?;
+        Self::absorb(&proof.commitment, transcript)?;
+        let challenge = Self::challenge(transcript);
+        let expected = Self::expected_commitment(pp, &statement, &response, challenge);
+
+        end_timer!(verifier_timer);
+
+        Ok(expected == proof.commitment)
+    }
+
+    /// The commitment `(a1, a2)` for which the response satisfies both verification equations
+    /// `<ck1.g, z> + <ck1.h, t1> = a1 d1^e` and `<ck2.g, z> + <ck2.h, t2> = a2 d2^e`
+    pub fn expected_commitment(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        response: &Response<C>,
+        challenge: C::ScalarField,
+    ) -> Commitment<C> {
+        let z = cfg_iter!(response.z)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+        let t1 = cfg_iter!(response.t1)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+        let t2 = cfg_iter!(response.t2)
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>();
+
+        let a1 =
+            C::msm_bigint(&pp.ck1.g, &z) + C::msm_bigint(&pp.ck1.h, &t1) - instance.d1 * challenge;
+        let a2 =
+            C::msm_bigint(&pp.ck2.g, &z) + C::msm_bigint(&pp.ck2.h, &t2) - instance.d2 * challenge;
+
+        Commitment {
+            a1: a1.into_affine(),
+            a2: a2.into_affine(),
+        }
+    }
+
+    /// Both keys must commit to messages of the same length
+    pub fn check_parameters(pp: &PublicParameters<C>) -> Result<(), Error> {
+        if pp.ck1.g.len() != pp.ck2.g.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.ck1.g.len(),
+                found: pp.ck2.g.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The response must match the lengths of the committing keys
+    pub fn check_response_shape(
+        pp: &PublicParameters<C>,
+        response: &Response<C>,
+    ) -> Result<(), Error> {
+        Self::check_parameters(pp)?;
+        if response.z.len() != pp.ck1.g.len()
+            || response.t1.len() != pp.ck1.h.len()
+            || response.t2.len() != pp.ck2.h.len()
+        {
+            return Err(Error::MalformedProof);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use ark_std::fmt;
+
+use crate::crypto::protocol::{channel::ChannelError, transcript::EncodingError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Two inputs which must have the same length do not
+    LengthMismatch { expected: usize, found: usize },
+    /// The number of generators must be a power of two
+    NonPowerOfTwo(usize),
+    /// A point to be absorbed into the transcript is the identity
+    IdentityPoint,
+    /// The proof does not have the shape expected by the public parameters
+    MalformedProof,
+    /// An element could not be appended to the transcript
+    TranscriptFailure,
+    /// The `index`-th proof of a batch does not verify
+    InvalidProof(usize),
+    /// The bit length of a range must be a power of two of at most 64
+    InvalidBitLength(usize),
+    /// The `index`-th value does not fit in the range
+    OutOfRange(usize),
+    /// A challenge derived from the transcript cannot be used, e.g. it is not invertible
+    DegenerateChallenge,
+    /// A message of an interactive protocol could not be exchanged
+    Channel(ChannelError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "length mismatch: expected {}, found {}", expected, found)
+            }
+            Self::NonPowerOfTwo(len) => write!(f, "length {} is not a power of two", len),
+            Self::IdentityPoint => write!(f, "unexpected identity point"),
+            Self::MalformedProof => write!(f, "malformed proof"),
+            Self::TranscriptFailure => write!(f, "failed to append to the transcript"),
+            Self::InvalidProof(index) => write!(f, "proof {} of the batch is invalid", index),
+            Self::InvalidBitLength(bits) => write!(f, "invalid bit length {} of a range", bits),
+            Self::OutOfRange(index) => write!(f, "value {} is out of range", index),
+            Self::DegenerateChallenge => write!(f, "degenerate challenge"),
+            Self::Channel(err) => write!(f, "channel failure: {}", err),
+        }
+    }
+}
+
+impl ark_std::error::Error for Error {}
+
+impl From<ChannelError> for Error {
+    fn from(err: ChannelError) -> Self {
+        Self::Channel(err)
+    }
+}
+
+impl From<EncodingError> for Error {
+    fn from(err: EncodingError) -> Self {
+        match err {
+            EncodingError::IdentityPoint => Self::IdentityPoint,
+            EncodingError::Serialization => Self::TranscriptFailure,
+        }
+    }
+}
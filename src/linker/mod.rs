@@ -1,4 +1,12 @@
 pub mod am_com_eq;
 pub mod am_eq;
+mod batch;
 pub mod comp_am_com_eq;
+pub mod comp_am_eq;
 pub mod comp_dl_eq;
+pub mod dual_eq;
+pub mod pok;
+pub mod range;
+
+pub mod errors;
+pub use errors::Error;
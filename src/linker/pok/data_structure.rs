@@ -0,0 +1,75 @@
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
+
+/// Committing key of the batch commitments, e.g. `ck.batch_g1`
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PublicParameters<C: CurveGroup> {
+    pub ck: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> Solidity for PublicParameters<C>
+where
+    C::Affine: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        let mut v = vec![self.ck.len().to_string()];
+        v.extend(self.ck.to_solidity());
+        v
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Instance<C: CurveGroup> {
+    pub commitment: C::Affine,
+}
+
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.point(&self.commitment, dest)
+    }
+}
+
+/// Opening `(m, r)` of the commitment, laid out as passed to `Pedersen::commit`
+/// with the randomness as its last elements.
+/// An opening shorter than the committing key is zero-padded, as in `batch_commit`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Witness<C: CurveGroup> {
+    pub opening: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Randomness<C: CurveGroup> {
+    pub r: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Response<C: CurveGroup> {
+    pub z: Vec<C::ScalarField>,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<C: CurveGroup> {
+    pub a: C::Affine,
+    pub z: Vec<C::ScalarField>,
+}
+
+impl<C: CurveGroup> Proof<C> {
+    pub fn from_conversation(a: C::Affine, response: Response<C>) -> Self {
+        Self { a, z: response.z }
+    }
+}
+
+impl<C: CurveGroup> Solidity for Proof<C>
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [self.z.to_solidity(), self.a.to_solidity()].concat()
+    }
+}
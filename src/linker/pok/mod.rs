@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, RngCore};
+
+pub mod data_structure;
+use crate::{
+    crypto::protocol::{
+        sigma::SigmaProtocol,
+        transcript::{AppendToTranscript, Points, TranscriptProtocol},
+    },
+    linker::Error,
+};
+
+pub use self::data_structure::*;
+
+mod prover;
+mod simulator;
+mod verifier;
+
+/// Schnorr proof of knowledge of the opening of a Pedersen commitment
+/// `cm = Π ck_i^(m_i)`, proving `<ck, z> = A cm^e` for the response `z = r + e m`
+pub struct Pok<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+impl<C: CurveGroup> SigmaProtocol for Pok<C> {
+    type PublicParameters = PublicParameters<C>;
+    type Instance = Instance<C>;
+    type Witness = Witness<C>;
+    type Proof = Proof<C>;
+
+    type Statement = Instance<C>;
+    type Commitment = C::Affine;
+    type State = Randomness<C>;
+    type Challenge = C::ScalarField;
+    type Response = Response<C>;
+
+    type Error = Error;
+
+    fn setup(pp: &Self::PublicParameters) -> Result<Self::PublicParameters, Self::Error> {
+        Ok(pp.clone())
+    }
+
+    fn prove<T: TranscriptProtocol, R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        witness: &Self::Witness,
+        transcript: &mut T,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Self::Error> {
+        Self::create_proof(pp, instance, witness, transcript, rng)
+    }
+
+    fn verify<T: TranscriptProtocol>(
+        pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> Result<bool, Self::Error> {
+        Self::verify_proof(pp, instance, proof, transcript)
+    }
+
+    fn bind<T: TranscriptProtocol>(
+        _pp: &Self::PublicParameters,
+        instance: &Self::Instance,
+        transcript: &mut T,
+    ) -> Result<Self::Statement, Self::Error> {
+        instance.append_to_transcript(b"instance", transcript)?;
+        Ok(instance.clone())
+    }
+
+    fn commit<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::State, Self::Commitment), Self::Error> {
+        Self::check_witness(pp, witness)?;
+        Ok(Self::create_random_commitment(pp, rng))
+    }
+
+    fn absorb<T: TranscriptProtocol>(
+        commitment: &Self::Commitment,
+        transcript: &mut T,
+    ) -> Result<(), Self::Error> {
+        Ok(Points(&[*commitment]).append_to_transcript(b"commitment", transcript)?)
+    }
+
+    fn challenge<T: TranscriptProtocol>(transcript: &mut T) -> Self::Challenge {
+        transcript.challenge_scalar(b"challenge")
+    }
+
+    fn respond(
+        pp: &Self::PublicParameters,
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::State,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Self::Error> {
+        Self::check_witness(pp, witness)?;
+        Ok(Self::compute_response(witness, &state, *challenge))
+    }
+
+    fn check(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Self::Error> {
+        Self::check_response_shape(pp, &response.z)?;
+        let expected = Self::expected_commitment(pp, statement, &response.z, *challenge);
+
+        Ok(expected == *commitment)
+    }
+
+    fn simulate<R: RngCore + CryptoRng>(
+        pp: &Self::PublicParameters,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Self::Error> {
+        Self::simulate_conversation(pp, statement, *challenge, rng)
+    }
+}
@@ -0,0 +1,79 @@
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::{
+    rand::{CryptoRng, Rng, RngCore},
+    Zero,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::{sigma::prove_with_phases, transcript::TranscriptProtocol},
+    linker::Error,
+};
+
+use super::{Instance, Pok, Proof, PublicParameters, Randomness, Response, Witness};
+
+impl<C: CurveGroup> Pok<C> {
+    /// Generate a random and compute the commitment `A = <ck, r>`
+    pub fn create_random_commitment(
+        pp: &PublicParameters<C>,
+        rng: &mut impl Rng,
+    ) -> (Randomness<C>, C::Affine) {
+        let commit_timer = start_timer!(|| "Pok::Commit");
+        let r = (0..pp.ck.len())
+            .map(|_| C::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let r_bigint = cfg_iter!(r).map(|s| s.into_bigint()).collect::<Vec<_>>();
+        let a = C::msm_bigint(&pp.ck, &r_bigint).into_affine();
+        end_timer!(commit_timer);
+
+        (Randomness { r }, a)
+    }
+
+    /// Compute the response `z = r + e m`, zero-padding the opening up to the key
+    pub fn compute_response(
+        witness: &Witness<C>,
+        randomness: &Randomness<C>,
+        challenge: C::ScalarField,
+    ) -> Response<C> {
+        let zero = C::ScalarField::zero();
+        let z = cfg_iter!(randomness.r)
+            .enumerate()
+            .map(|(i, r)| *r + challenge * witness.opening.get(i).unwrap_or(&zero))
+            .collect();
+
+        Response { z }
+    }
+
+    /// Prove knowledge of the opening of `cm`, absorbing the instance before `A`
+    pub fn create_proof<R: RngCore + CryptoRng>(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let proof_timer = start_timer!(|| "Pok::Prover");
+
+        let (a, response) =
+            prove_with_phases::<Self, _, _>(pp, instance, witness, transcript, rng)?;
+
+        end_timer!(proof_timer);
+
+        Ok(Proof::from_conversation(a, response))
+    }
+
+    /// The opening must fit in the committing key
+    pub fn check_witness(pp: &PublicParameters<C>, witness: &Witness<C>) -> Result<(), Error> {
+        if witness.opening.len() > pp.ck.len() {
+            return Err(Error::LengthMismatch {
+                expected: pp.ck.len(),
+                found: witness.opening.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
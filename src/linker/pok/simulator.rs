@@ -0,0 +1,28 @@
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_std::rand::Rng;
+
+use crate::linker::Error;
+
+use super::{Instance, Pok, PublicParameters, Response};
+
+impl<C: CurveGroup> Pok<C> {
+    /// Simulate an accepting conversation for the challenge without the opening,
+    /// by sampling the response and setting `A = <ck, z> cm^(-e)`
+    pub fn simulate_conversation(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        challenge: C::ScalarField,
+        rng: &mut impl Rng,
+    ) -> Result<(C::Affine, Response<C>), Error> {
+        let response = Response {
+            z: (0..pp.ck.len())
+                .map(|_| C::ScalarField::rand(rng))
+                .collect(),
+        };
+        Self::check_response_shape(pp, &response.z)?;
+        let a = Self::expected_commitment(pp, instance, &response.z, challenge);
+
+        Ok((a, response))
+    }
+}
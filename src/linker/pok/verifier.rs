@@ -0,0 +1,96 @@
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::{sigma::SigmaProtocol, transcript::TranscriptProtocol},
+    linker::{batch, Error},
+};
+
+use super::{Instance, Pok, Proof, PublicParameters};
+
+impl<C: CurveGroup> Pok<C> {
+    pub fn verify_proof(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        Self::check_response_shape(pp, &proof.z)?;
+
+        let verifier_timer = start_timer!(|| "Pok::Verifier");
+
+        let statement = Self::bind(pp, instance, transcript)?;
+        Self::absorb(&proof.a, transcript)?;
+        let challenge = Self::challenge(transcript);
+        let expected = Self::expected_commitment(pp, &statement, &proof.z, challenge);
+
+        end_timer!(verifier_timer);
+
+        Ok(expected == proof.a)
+    }
+
+    /// The commitment `A` for which the response satisfies `<ck, z> = A cm^e`
+    pub fn expected_commitment(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        z: &[C::ScalarField],
+        challenge: C::ScalarField,
+    ) -> C::Affine {
+        let z = cfg_iter!(z).map(|s| s.into_bigint()).collect::<Vec<_>>();
+
+        (C::msm_bigint(&pp.ck, &z) - instance.commitment * challenge).into_affine()
+    }
+
+    /// Verify a batch of openings with one multi-scalar multiplication over `ck`,
+    /// weighting `<ck, z> = A cm^e` of each proof by a fresh random scalar.
+    /// Each transcript is the verifier transcript of its proof.
+    pub fn verify_batch<T: TranscriptProtocol>(
+        pp: &PublicParameters<C>,
+        proofs: &[(Instance<C>, Proof<C>, T)],
+        rng: &mut impl Rng,
+    ) -> Result<(), Error> {
+        let verifier_timer = start_timer!(|| "Pok::Batch Verifier");
+
+        let result = batch::verify_batch::<C, _, _, _, _>(
+            &pp.ck,
+            proofs,
+            rng,
+            |equation, instance, proof, mut transcript, rng| {
+                Self::check_response_shape(pp, &proof.z)?;
+
+                Self::bind(pp, instance, &mut transcript)?;
+                Self::absorb(&proof.a, &mut transcript)?;
+                let challenge = Self::challenge(&mut transcript);
+                let rho = C::ScalarField::rand(rng);
+
+                // rho * (<ck, z> - A - cm^e)
+                for (s, z) in equation.shared.iter_mut().zip(&proof.z) {
+                    *s += rho * z;
+                }
+                equation.bases.extend([proof.a, instance.commitment]);
+                equation.scalars.extend([-rho, -rho * challenge]);
+                Ok(())
+            },
+            |instance, proof, transcript| Self::verify_proof(pp, instance, proof, transcript),
+        );
+        end_timer!(verifier_timer);
+
+        result
+    }
+
+    /// The response must match the length of the committing key
+    pub fn check_response_shape(
+        pp: &PublicParameters<C>,
+        z: &[C::ScalarField],
+    ) -> Result<(), Error> {
+        if z.len() != pp.ck.len() {
+            return Err(Error::MalformedProof);
+        }
+
+        Ok(())
+    }
+}
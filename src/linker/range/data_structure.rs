@@ -0,0 +1,97 @@
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Encoding, EncodingError},
+    solidity::Solidity,
+};
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PublicParameters<C: CurveGroup> {
+    /// Generators of the value commitments `V = g^v h^γ`, as in `Pedersen::commit(&[g, h], ..)`
+    pub g: C::Affine,
+    pub h: C::Affine,
+    /// Vector generators, one pair per bit of the aggregated values
+    pub gs: Vec<C::Affine>,
+    pub hs: Vec<C::Affine>,
+    /// Generator of the inner product
+    pub u: C::Affine,
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Instance<C: CurveGroup> {
+    /// Commitments to values in `[0, 2^bits)`
+    pub commitments: Vec<C::Affine>,
+    pub bits: usize,
+}
+
+impl<C: CurveGroup> AppendToTranscript for Instance<C> {
+    fn encode(&self, encoding: Encoding, dest: &mut Vec<u8>) -> Result<(), EncodingError> {
+        encoding.points(&self.commitments, dest)?;
+        (self.bits as u64).encode(encoding, dest)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Witness<C: CurveGroup> {
+    pub values: Vec<u64>,
+    pub gamma: Vec<C::ScalarField>,
+}
+
+/// Inner-product argument folded down to two elements, as in `CompDLEq`.
+/// `b` is kept in reverse order, see `RangeProof::prove_inner_product`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct InnerProductProof<C: CurveGroup> {
+    pub left: Vec<C::Affine>,
+    pub right: Vec<C::Affine>,
+    pub a: Vec<C::ScalarField>,
+    pub b: Vec<C::ScalarField>,
+}
+
+impl<C: CurveGroup> Solidity for InnerProductProof<C>
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [
+            self.a.to_solidity(),
+            self.b.to_solidity(),
+            self.left.to_solidity(),
+            self.right.to_solidity(),
+        ]
+        .concat()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<C: CurveGroup> {
+    pub a: C::Affine,
+    pub s: C::Affine,
+    pub t1: C::Affine,
+    pub t2: C::Affine,
+    pub t_hat: C::ScalarField,
+    pub tau_x: C::ScalarField,
+    pub mu: C::ScalarField,
+    pub ipa: InnerProductProof<C>,
+}
+
+impl<C: CurveGroup> Solidity for Proof<C>
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    fn to_solidity(&self) -> Vec<String> {
+        [
+            self.t_hat.to_solidity(),
+            self.tau_x.to_solidity(),
+            self.mu.to_solidity(),
+            self.a.to_solidity(),
+            self.s.to_solidity(),
+            self.t1.to_solidity(),
+            self.t2.to_solidity(),
+            self.ipa.to_solidity(),
+        ]
+        .concat()
+    }
+}
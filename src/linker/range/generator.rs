@@ -0,0 +1,35 @@
+use ark_ec::CurveGroup;
+use ark_std::rand::Rng;
+
+use crate::crypto::commitment::{pedersen::Pedersen, CommitmentScheme};
+
+use super::{PublicParameters, RangeProof};
+
+impl<C: CurveGroup> RangeProof<C> {
+    /// Public parameters for up to `capacity` aggregated bits over the value generators `(g, h)`
+    pub fn generate_public_parameters(
+        g: C::Affine,
+        h: C::Affine,
+        capacity: usize,
+        rng: &mut impl Rng,
+    ) -> PublicParameters<C> {
+        let mut generators = (0..2 * capacity + 1)
+            .map(|_| C::rand(rng))
+            .collect::<Vec<_>>();
+        let u = generators.pop().unwrap_or_else(C::generator);
+        let hs = generators.split_off(capacity);
+
+        PublicParameters {
+            g,
+            h,
+            gs: C::normalize_batch(&generators),
+            hs: C::normalize_batch(&hs),
+            u: u.into_affine(),
+        }
+    }
+
+    /// Commitment `g^v h^γ` to a value
+    pub fn commit(pp: &PublicParameters<C>, value: u64, gamma: C::ScalarField) -> C::Affine {
+        Pedersen::<C>::commit(&[pp.g, pp.h], &[C::ScalarField::from(value), gamma])
+    }
+}
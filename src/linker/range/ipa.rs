@@ -0,0 +1,137 @@
+use ark_ec::CurveGroup;
+use ark_ff::Field;
+
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Points, TranscriptProtocol},
+    linker::{
+        comp_dl_eq::{CompDLEq, MultiPublicParameters, Witness as FoldingWitness},
+        Error,
+    },
+};
+
+use super::{InnerProductProof, RangeProof};
+
+impl<C: CurveGroup> RangeProof<C> {
+    /// Prove knowledge of `a` and `b` such that `P = <G, a> + <H, b> + <a, b> U`.
+    ///
+    /// Each round halves the vectors with the challenge `e`, as `CompDLEq` does:
+    /// `G' = e G_l + G_r`, `a' = a_l + e a_r` and `P' = L + e P + e^2 R`.
+    /// Keeping `H` and `b` in reverse order makes them fold as `H' = H_l + e H_r`
+    /// and `b' = e b_l + b_r`, which preserves the inner product term,
+    /// so that all four vectors go through the halving of `CompDLEq`.
+    pub fn prove_inner_product(
+        gs: &[C::Affine],
+        hs: &[C::Affine],
+        u: &C::Affine,
+        a: &[C::ScalarField],
+        b: &[C::ScalarField],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<InnerProductProof<C>, Error> {
+        let threshold = 2;
+        let n = gs.len();
+        if hs.len() != n || a.len() != n || b.len() != n {
+            return Err(Error::LengthMismatch {
+                expected: n,
+                found: hs.len().min(a.len()).min(b.len()),
+            });
+        }
+        if n < threshold || !n.is_power_of_two() {
+            return Err(Error::NonPowerOfTwo(n));
+        }
+
+        let prover_timer = start_timer!(|| "RangeProof::Inner Product Prover");
+        let mut pp = MultiPublicParameters::<C> {
+            bases: vec![gs.to_vec(), hs.iter().rev().cloned().collect()],
+        };
+        let mut a = FoldingWitness::<C> { z: a.to_vec() };
+        let mut b = FoldingWitness::<C> {
+            z: b.iter().rev().cloned().collect(),
+        };
+
+        let (mut left, mut right) = (vec![], vec![]);
+        while pp.bases[0].len() > threshold {
+            let mid = pp.bases[0].len() / 2;
+            let (a_l, a_r) = a.z.split_at(mid);
+            let (b_l, b_r) = b.z.split_at(mid);
+
+            // <G_r, a_l> + <H_l, b_r> + <a_l, b_r> U, written over the reversed H and b
+            let l = Self::msm(&pp.bases[0][mid..], a_l)
+                + Self::msm(&pp.bases[1][mid..], b_l)
+                + *u * Self::reversed_inner_product(a_l, b_l);
+            let r = Self::msm(&pp.bases[0][..mid], a_r)
+                + Self::msm(&pp.bases[1][..mid], b_r)
+                + *u * Self::reversed_inner_product(a_r, b_r);
+            let lr = C::normalize_batch(&[l, r]);
+
+            Points(&lr).append_to_transcript(b"commitments", transcript)?;
+            let challenge = transcript.challenge_scalar(b"challenge");
+
+            pp = CompDLEq::update_public_parameters(&pp, challenge)?;
+            a = CompDLEq::update_witness(&a, challenge)?;
+            b = CompDLEq::update_witness(&b, challenge)?;
+            left.push(lr[0]);
+            right.push(lr[1]);
+        }
+        end_timer!(prover_timer);
+
+        Ok(InnerProductProof {
+            left,
+            right,
+            a: a.z,
+            b: b.z,
+        })
+    }
+
+    /// Verify an inner-product argument for `p`, updating the generators once at the end
+    pub fn verify_inner_product(
+        gs: &[C::Affine],
+        hs: &[C::Affine],
+        u: &C::Affine,
+        p: C,
+        proof: &InnerProductProof<C>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        let threshold = 2;
+        let n = gs.len();
+        if hs.len() != n {
+            return Err(Error::LengthMismatch {
+                expected: n,
+                found: hs.len(),
+            });
+        }
+        if n < threshold || !n.is_power_of_two() {
+            return Err(Error::NonPowerOfTwo(n));
+        }
+        // one round per halving of the generators down to the threshold
+        if proof.a.len() != threshold
+            || proof.b.len() != threshold
+            || proof.left.len() != proof.right.len()
+            || (n / threshold).trailing_zeros() as usize != proof.left.len()
+        {
+            return Err(Error::MalformedProof);
+        }
+
+        let verifier_timer = start_timer!(|| "RangeProof::Inner Product Verifier");
+        let mut p = p;
+        let mut challenges = vec![];
+        for (l, r) in proof.left.iter().zip(&proof.right) {
+            Points(&[*l, *r]).append_to_transcript(b"commitments", transcript)?;
+            let challenge = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+            challenges.push(challenge);
+
+            p = *l + p * challenge + *r * challenge.square();
+        }
+
+        let pp = MultiPublicParameters::<C> {
+            bases: vec![gs.to_vec(), hs.iter().rev().cloned().collect()],
+        };
+        let pp = CompDLEq::update_public_parameters_once(&pp, &challenges)?;
+
+        let expected = Self::msm(&pp.bases[0], &proof.a)
+            + Self::msm(&pp.bases[1], &proof.b)
+            + *u * Self::reversed_inner_product(&proof.a, &proof.b);
+        end_timer!(verifier_timer);
+
+        Ok(p == expected)
+    }
+}
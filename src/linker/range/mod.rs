@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField};
+use ark_std::rand::Rng;
+
+pub mod data_structure;
+pub use self::data_structure::*;
+
+use crate::{
+    crypto::protocol::transcript::{bind_context, AppendToTranscript, Context, TranscriptProtocol},
+    linker::Error,
+};
+
+mod generator;
+mod ipa;
+mod prover;
+mod verifier;
+
+/// Bulletproofs range proof that each committed value lies in `[0, 2^bits)`,
+/// aggregated over all the commitments of the instance.
+/// The inner-product argument reuses the recursive halving of `CompDLEq`.
+pub struct RangeProof<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+impl<C: CurveGroup> RangeProof<C> {
+    /// Check the shape of the instance against the public parameters
+    /// and return the number of aggregated bits
+    pub fn check_instance(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+    ) -> Result<usize, Error> {
+        let (bits, m) = (instance.bits, instance.commitments.len());
+        if !bits.is_power_of_two() || bits > 64 {
+            return Err(Error::InvalidBitLength(bits));
+        }
+        if !m.is_power_of_two() {
+            return Err(Error::NonPowerOfTwo(m));
+        }
+
+        // the inner-product argument halves the vectors at least once
+        let nm = bits * m;
+        if nm < 2 {
+            return Err(Error::InvalidBitLength(bits));
+        }
+        if pp.gs.len() < nm || pp.hs.len() < nm {
+            return Err(Error::LengthMismatch {
+                expected: nm,
+                found: pp.gs.len().min(pp.hs.len()),
+            });
+        }
+
+        Ok(nm)
+    }
+
+    /// Absorb the commitments and the bit length into the transcript
+    pub fn bind<T: TranscriptProtocol>(
+        instance: &Instance<C>,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
+        Ok(instance.append_to_transcript(b"instance", transcript)?)
+    }
+
+    /// `create_proof` bound to `context`, as `SigmaProtocol::prove_with_context`
+    pub fn create_proof_with_context(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        context: Option<&Context>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl Rng,
+    ) -> Result<Proof<C>, Error> {
+        bind_context(context, transcript);
+        Self::create_proof(pp, instance, witness, transcript, rng)
+    }
+
+    pub fn verify_proof_with_context(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+        context: Option<&Context>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        bind_context(context, transcript);
+        Self::verify_proof(pp, instance, proof, transcript)
+    }
+
+    /// `[1, x, ..., x^(n - 1)]`
+    fn powers(x: C::ScalarField, n: usize) -> Vec<C::ScalarField> {
+        let mut powers = vec![];
+        let mut curr = C::ScalarField::ONE;
+        for _ in 0..n {
+            powers.push(curr);
+            curr *= x;
+        }
+        powers
+    }
+
+    /// `z^(2 + j) 2^i` at position `j * bits + i`, the weights of the bits of the `j`-th value
+    fn weights(z: C::ScalarField, bits: usize, m: usize) -> Vec<C::ScalarField> {
+        let powers_of_two = Self::powers(C::ScalarField::from(2u64), bits);
+        let z_square = z.square();
+        Self::powers(z, m)
+            .iter()
+            .flat_map(|z_j| powers_of_two.iter().map(move |p| z_square * z_j * p))
+            .collect()
+    }
+
+    /// `Σ a_i b_(n - 1 - i)`, the inner product of `a` with the reversed `b`
+    fn reversed_inner_product(a: &[C::ScalarField], b: &[C::ScalarField]) -> C::ScalarField {
+        a.iter().zip(b.iter().rev()).map(|(a, b)| *a * b).sum()
+    }
+
+    fn msm(bases: &[C::Affine], scalars: &[C::ScalarField]) -> C {
+        let scalars = scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>();
+        C::msm_bigint(bases, &scalars)
+    }
+}
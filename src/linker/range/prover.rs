@@ -0,0 +1,148 @@
+use ark_ec::CurveGroup;
+use ark_ff::{Field, UniformRand};
+use ark_std::rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Points, Scalars, TranscriptProtocol},
+    linker::Error,
+};
+
+use super::{Instance, Proof, PublicParameters, RangeProof, Witness};
+
+impl<C: CurveGroup> RangeProof<C> {
+    pub fn create_proof(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        witness: &Witness<C>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl Rng,
+    ) -> Result<Proof<C>, Error> {
+        let nm = Self::check_instance(pp, instance)?;
+        let (bits, m) = (instance.bits, instance.commitments.len());
+        if witness.values.len() != m || witness.gamma.len() != m {
+            return Err(Error::LengthMismatch {
+                expected: m,
+                found: witness.values.len().min(witness.gamma.len()),
+            });
+        }
+        if let Some(index) = witness
+            .values
+            .iter()
+            .position(|v| bits < 64 && *v >> bits != 0)
+        {
+            return Err(Error::OutOfRange(index));
+        }
+
+        let prover_timer = start_timer!(|| "RangeProof::Prover");
+        let (gs, hs) = (&pp.gs[..nm], &pp.hs[..nm]);
+        Self::bind(instance, transcript)?;
+
+        let commit_timer = start_timer!(|| "Commit to the bits");
+        let one = C::ScalarField::ONE;
+        let a_l = witness
+            .values
+            .iter()
+            .flat_map(|v| (0..bits).map(move |i| C::ScalarField::from((v >> i) & 1)))
+            .collect::<Vec<_>>();
+        let a_r = cfg_iter!(a_l).map(|a| *a - one).collect::<Vec<_>>();
+        let alpha = C::ScalarField::rand(rng);
+        let a = pp.h * alpha + Self::msm(gs, &a_l) + Self::msm(hs, &a_r);
+
+        let s_l = (0..nm)
+            .map(|_| C::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let s_r = (0..nm)
+            .map(|_| C::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let rho = C::ScalarField::rand(rng);
+        let s = pp.h * rho + Self::msm(gs, &s_l) + Self::msm(hs, &s_r);
+        let a_s = C::normalize_batch(&[a, s]);
+        end_timer!(commit_timer);
+
+        Points(&a_s).append_to_transcript(b"commitment", transcript)?;
+        let y = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+        let z = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+        let y_inv = y.inverse().ok_or(Error::DegenerateChallenge)?;
+
+        // l(X) = (a_L - z) + s_L X, r(X) = y^i (a_R + z + s_R X) + z^(2 + j) 2^i
+        let polynomial_timer = start_timer!(|| "Compute t(X)");
+        let powers_of_y = Self::powers(y, nm);
+        let weights = Self::weights(z, bits, m);
+        let l0 = cfg_iter!(a_l).map(|a| *a - z).collect::<Vec<_>>();
+        let r0 = cfg_iter!(a_r)
+            .zip(&powers_of_y)
+            .zip(&weights)
+            .map(|((a, y), w)| *y * (*a + z) + w)
+            .collect::<Vec<_>>();
+        let r1 = cfg_iter!(s_r)
+            .zip(&powers_of_y)
+            .map(|(s, y)| *y * s)
+            .collect::<Vec<_>>();
+        let inner_product = |a: &[C::ScalarField], b: &[C::ScalarField]| {
+            cfg_iter!(a)
+                .zip(b)
+                .map(|(a, b)| *a * b)
+                .sum::<C::ScalarField>()
+        };
+        let t1 = inner_product(&l0, &r1) + inner_product(&s_l, &r0);
+        let t2 = inner_product(&s_l, &r1);
+
+        let tau1 = C::ScalarField::rand(rng);
+        let tau2 = C::ScalarField::rand(rng);
+        let t = C::normalize_batch(&[pp.g * t1 + pp.h * tau1, pp.g * t2 + pp.h * tau2]);
+        end_timer!(polynomial_timer);
+
+        Points(&t).append_to_transcript(b"commitment", transcript)?;
+        let x = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+
+        let l = cfg_iter!(l0)
+            .zip(&s_l)
+            .map(|(l, s)| *l + x * s)
+            .collect::<Vec<_>>();
+        let r = cfg_iter!(r0)
+            .zip(&r1)
+            .map(|(r, s)| *r + x * s)
+            .collect::<Vec<_>>();
+        let t_hat = inner_product(&l, &r);
+        let tau_x = tau2 * x.square()
+            + tau1 * x
+            + Self::powers(z, m)
+                .iter()
+                .zip(&witness.gamma)
+                .map(|(z_j, gamma)| z.square() * z_j * gamma)
+                .sum::<C::ScalarField>();
+        let mu = alpha + rho * x;
+
+        Scalars(&[t_hat, tau_x, mu]).append_to_transcript(b"evaluation", transcript)?;
+        let w = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+
+        // H'_i = H_i y^(-i)
+        let hs_prime = cfg_iter!(hs)
+            .zip(Self::powers(y_inv, nm))
+            .map(|(h, y)| *h * y)
+            .collect::<Vec<_>>();
+        let ipa = Self::prove_inner_product(
+            gs,
+            &C::normalize_batch(&hs_prime),
+            &(pp.u * w).into_affine(),
+            &l,
+            &r,
+            transcript,
+        )?;
+        end_timer!(prover_timer);
+
+        Ok(Proof {
+            a: a_s[0],
+            s: a_s[1],
+            t1: t[0],
+            t2: t[1],
+            t_hat,
+            tau_x,
+            mu,
+            ipa,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    crypto::protocol::transcript::{AppendToTranscript, Points, Scalars, TranscriptProtocol},
+    linker::Error,
+};
+
+use super::{Instance, Proof, PublicParameters, RangeProof};
+
+impl<C: CurveGroup> RangeProof<C> {
+    pub fn verify_proof(
+        pp: &PublicParameters<C>,
+        instance: &Instance<C>,
+        proof: &Proof<C>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<bool, Error> {
+        let nm = Self::check_instance(pp, instance)?;
+        let (bits, m) = (instance.bits, instance.commitments.len());
+
+        let verifier_timer = start_timer!(|| "RangeProof::Verifier");
+        let (gs, hs) = (&pp.gs[..nm], &pp.hs[..nm]);
+        Self::bind(instance, transcript)?;
+
+        Points(&[proof.a, proof.s]).append_to_transcript(b"commitment", transcript)?;
+        let y = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+        let z = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+        let y_inv = y.inverse().ok_or(Error::DegenerateChallenge)?;
+
+        Points(&[proof.t1, proof.t2]).append_to_transcript(b"commitment", transcript)?;
+        let x = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+
+        Scalars(&[proof.t_hat, proof.tau_x, proof.mu])
+            .append_to_transcript(b"evaluation", transcript)?;
+        let w = transcript.challenge_scalar::<C::ScalarField>(b"challenge");
+
+        // g^t_hat h^tau_x = Π V_j^(z^(2 + j)) g^δ(y, z) T1^x T2^(x^2), where
+        // δ(y, z) = (z - z^2) Σ y^i - Σ z^(3 + j) (2^bits - 1)
+        let polynomial_timer = start_timer!(|| "Check t(x)");
+        let powers_of_y = Self::powers(y, nm);
+        let powers_of_z = Self::powers(z, m);
+        let z_square = z.square();
+        let sum_of_two = C::ScalarField::from(2u64).pow([bits as u64]) - C::ScalarField::ONE;
+        let delta = (z - z_square) * powers_of_y.iter().sum::<C::ScalarField>()
+            - powers_of_z.iter().sum::<C::ScalarField>() * z_square * z * sum_of_two;
+        let v = Self::msm(
+            &instance.commitments,
+            &powers_of_z
+                .iter()
+                .map(|z_j| z_square * z_j)
+                .collect::<Vec<_>>(),
+        );
+        let t_valid = pp.g * (proof.t_hat - delta) + pp.h * proof.tau_x
+            == v + proof.t1 * x + proof.t2 * x.square();
+        end_timer!(polynomial_timer);
+
+        // P = A S^x G^(-z) H^(z + z^(2 + j) 2^i y^(-i)) h^(-μ) U'^t_hat
+        let p_timer = start_timer!(|| "Compute P");
+        let powers_of_y_inv = Self::powers(y_inv, nm);
+        let weights = Self::weights(z, bits, m);
+        let h_scalars = cfg_iter!(weights)
+            .zip(&powers_of_y_inv)
+            .map(|(w, y)| (z + *w * y).into_bigint())
+            .collect::<Vec<_>>();
+        let u = pp.u * w;
+        let p = proof.a + proof.s * x - Self::msm(gs, &vec![z; nm]) + C::msm_bigint(hs, &h_scalars)
+            - pp.h * proof.mu
+            + u * proof.t_hat;
+
+        let hs_prime = cfg_iter!(hs)
+            .zip(powers_of_y_inv)
+            .map(|(h, y)| *h * y)
+            .collect::<Vec<_>>();
+        end_timer!(p_timer);
+
+        let ipa_valid = Self::verify_inner_product(
+            gs,
+            &C::normalize_batch(&hs_prime),
+            &u.into_affine(),
+            p,
+            &proof.ipa,
+            transcript,
+        )?;
+        end_timer!(verifier_timer);
+
+        Ok(t_valid && ipa_valid)
+    }
+}
@@ -38,7 +38,7 @@ use crate::{
 };
 
 #[derive(Clone)]
-struct BatchCommitmentCircuit<C: CurveGroup> {
+pub(super) struct BatchCommitmentCircuit<C: CurveGroup> {
     // public input
     pub tau: Option<C::ScalarField>,
 
@@ -50,7 +50,7 @@ struct BatchCommitmentCircuit<C: CurveGroup> {
 impl<C: CurveGroup> BatchCommitmentCircuit<C> {
     pub fn new(commitments: Vec<Vec<C::ScalarField>>, tau: C::ScalarField) -> Self {
         let slices: Vec<&[C::ScalarField]> = commitments.iter().map(|cm| &cm[..]).collect();
-        let (aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices[..], tau, None);
+        let (aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices[..], 2, tau, None).unwrap();
 
         Self {
             tau: Some(tau),
@@ -133,12 +133,14 @@ impl<C: CurveGroup> ZKSTCircuit<C> {
             .concat()
             .transpose();
         let slices: Vec<&[C::ScalarField]> = public_inputs.iter().map(|x| &x[..]).collect();
-        let (x_aggregation, initial) = Pedersen::<C>::scalar_aggregate(&slices, tau, None);
+        let (x_aggregation, initial) =
+            Pedersen::<C>::scalar_aggregate(&slices, 1, tau, None).unwrap();
         drop(public_inputs);
 
         let commitments = [&current_commitments[..], &delta_commitments[..]].concat();
         let slices: Vec<&[C::ScalarField]> = commitments.iter().map(|cm| &cm[..]).collect();
-        let (mut aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices, tau, Some(initial));
+        let (mut aggregation, _) =
+            Pedersen::<C>::scalar_aggregate(&slices, 2, tau, Some(initial)).unwrap();
         aggregation[0] += x_aggregation[0];
 
         Self {
@@ -225,12 +227,8 @@ impl<C: CurveGroup> ConstraintSynthesizer<C::ScalarField> for ZKSTCircuit<C> {
             )
             .unwrap();
 
-        let public_inputs = vec![vec![flag], permuted]
-            .concat()
-            .transpose()
-            .iter()
-            .map(|x| vec![x[0].clone(), FpVar::Constant(C::ScalarField::zero())])
-            .collect();
+        // public inputs are single-slot messages, zero-padded by the aggregation
+        let public_inputs = vec![vec![flag], permuted].concat().transpose();
         let commitments = [public_inputs, current_commitments, delta_commitments]
             .concat::<Vec<FpVar<C::ScalarField>>>();
         PedersenGadget::<C, FpVar<C::ScalarField>>::enforce_equal(
@@ -245,7 +243,10 @@ impl<C: CurveGroup> ConstraintSynthesizer<C::ScalarField> for ZKSTCircuit<C> {
     }
 }
 
-fn test_commitments<F: PrimeField>(num_commitments: usize, length: usize) -> Vec<Vec<F>> {
+pub(super) fn test_commitments<F: PrimeField>(
+    num_commitments: usize,
+    length: usize,
+) -> Vec<Vec<F>> {
     let mut commitments = vec![];
     for i in 0..num_commitments {
         // let value = ((i & 1) + 1) as u64;
@@ -326,7 +327,8 @@ where
 
         // Batch Commitment Module
         let slices = cfg_iter!(commitments).map(|cm| &cm[..]).collect::<Vec<_>>();
-        let commitments_g1 = Pedersen::<E::G1>::batch_commit(&pk.vk.ck.batch_g1, &slices[..]);
+        let commitments_g1 =
+            Pedersen::<E::G1>::batch_commit(&pk.vk.ck.batch_g1, &slices[..]).unwrap();
         let tau =
             Pedersen::<E::G1>::challenge(&[], &commitments_g1, &proof_dependent_commitment.cm);
 
@@ -351,7 +353,7 @@ where
         let vry_instant = Instant::now();
         let agg_instant = Instant::now();
         // Aggregate commitments
-        let (aggregation_g1, _) = Pedersen::<E::G1>::aggregate(&commitments_g1, tau, None);
+        let (aggregation_g1, _) = Pedersen::<E::G1>::aggregate(&commitments_g1, tau, None).unwrap();
         // Update proof dependent commitment
         proof.d = (proof.d.into_group() + aggregation_g1).into_affine();
         aggregation.push(agg_instant.elapsed().as_micros());
@@ -425,7 +427,8 @@ where
 
         // Batch Commitment Module
         let slices = cfg_iter!(commitments).map(|cm| &cm[..]).collect::<Vec<_>>();
-        let commitments_g1 = Pedersen::<E::G1>::batch_commit(&pk.vk.ck.batch_g1, &slices[..]);
+        let commitments_g1 =
+            Pedersen::<E::G1>::batch_commit(&pk.vk.ck.batch_g1, &slices[..]).unwrap();
         let tau =
             Pedersen::<E::G1>::challenge(&[], &commitments_g1, &proof_dependent_commitment.cm);
 
@@ -473,7 +476,7 @@ where
 
         let agg_instant = Instant::now();
         // Aggregate commitments
-        let (aggregation_g1, _) = Pedersen::<E::G1>::aggregate(&commitments_g1, tau, None);
+        let (aggregation_g1, _) = Pedersen::<E::G1>::aggregate(&commitments_g1, tau, None).unwrap();
         // Update proof dependent commitment
         dlc_proof.d = (dlc_proof.d.into_group() + aggregation_g1).into_affine();
         aggregation.push(agg_instant.elapsed().as_micros());
@@ -536,7 +539,7 @@ fn zkst_circuit_commit<E: Pairing, R: RngCore + CryptoRng>(
 
     // Batch Commitment Module
     let slices = cfg_iter!(commitments).map(|cm| &cm[..]).collect::<Vec<_>>();
-    let commitments_g1 = Pedersen::<E::G1>::batch_commit(&ck.batch_g1, &slices);
+    let commitments_g1 = Pedersen::<E::G1>::batch_commit(&ck.batch_g1, &slices).unwrap();
     let tau = Pedersen::<E::G1>::challenge(
         public_inputs,
         &commitments_g1[batch_size..],
@@ -564,8 +567,10 @@ fn zkst_circuit_prove_and_verify<E: Pairing, R: RngCore + CryptoRng>(
     // Aggregate commitments
     let committed = committed.to_vec().transpose();
     let slices = cfg_iter!(committed).map(|x| &x[..]).collect::<Vec<_>>();
-    let (aggregation_fr, initial) = Pedersen::<E::G1>::scalar_aggregate(&slices, tau, None);
-    let (aggregation_g1, _) = Pedersen::<E::G1>::aggregate(commitments, tau, Some(initial));
+    let (aggregation_fr, initial) =
+        Pedersen::<E::G1>::scalar_aggregate(&slices, 1, tau, None).unwrap();
+    let (aggregation_g1, _) =
+        Pedersen::<E::G1>::aggregate(commitments, tau, Some(initial)).unwrap();
     // Update proof dependent commitment
     let mut verify = proof.clone();
     let aggregation = aggregation_g1 + vk.ck.batch_g1[0].into_group() * aggregation_fr[0];
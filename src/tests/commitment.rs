@@ -0,0 +1,635 @@
+use ark_ec::{
+    twisted_edwards::{Affine as TEAffine, TECurveConfig},
+    CurveGroup,
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::curves::twisted_edwards::AffineVar,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::{marker::PhantomData, vec::Vec};
+
+use crate::crypto::commitment::{
+    pedersen::{EmbeddedPedersenGadget, Pedersen, PedersenGadget},
+    registry::{MembershipProof, MembershipProofVar, RegistryGadget},
+    BatchCommitmentGadget, BatchCommitmentScheme, CommitmentGadget, HomomorphicCommitmentGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+
+#[derive(Clone)]
+struct UpdateCircuit<C: CurveGroup> {
+    // committed witness
+    pub old: Option<Vec<C::ScalarField>>,
+    pub delta: Option<Vec<C::ScalarField>>,
+    pub new: Option<Vec<C::ScalarField>>,
+}
+
+impl<C: CurveGroup> ConstraintSynthesizer<C::ScalarField> for UpdateCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<C::ScalarField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let old = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.old.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let delta = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.delta.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let new = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.new.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        PedersenGadget::<C, FpVar<C::ScalarField>>::enforce_update(new, old, delta)
+    }
+}
+
+#[derive(Clone)]
+struct AggregationCircuit<C: CurveGroup, ConstraintF: PrimeField, FV> {
+    // public input
+    pub tau: Option<C::ScalarField>,
+
+    // committed witness
+    pub aggregation: Option<Vec<C::ScalarField>>,
+    pub commitments: Option<Vec<Vec<C::ScalarField>>>,
+
+    _var: PhantomData<(ConstraintF, FV)>,
+}
+
+impl<C: CurveGroup, ConstraintF: PrimeField, FV> AggregationCircuit<C, ConstraintF, FV> {
+    pub fn new(commitments: Vec<Vec<C::ScalarField>>, tau: C::ScalarField) -> Self {
+        let width = commitments.iter().map(|cm| cm.len()).max().unwrap_or(0);
+        let slices: Vec<&[C::ScalarField]> = commitments.iter().map(|cm| &cm[..]).collect();
+        let (aggregation, _) =
+            Pedersen::<C>::scalar_aggregate(&slices[..], width, tau, None).unwrap();
+
+        Self {
+            tau: Some(tau),
+            aggregation: Some(aggregation),
+            commitments: Some(commitments),
+            _var: PhantomData,
+        }
+    }
+}
+
+impl<C, ConstraintF, FV> ConstraintSynthesizer<ConstraintF>
+    for AggregationCircuit<C, ConstraintF, FV>
+where
+    C: CurveGroup,
+    ConstraintF: PrimeField,
+    FV: FieldVar<C::ScalarField, ConstraintF>,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<ConstraintF>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let tau = FV::new_input(cs.clone(), || {
+            self.tau.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let aggregation = Vec::<FV>::new_witness(cs.clone(), || {
+            self.aggregation.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let commitments = self
+            .commitments
+            .ok_or(SynthesisError::AssignmentMissing)?
+            .into_iter()
+            .map(|cm| Vec::<FV>::new_witness(cs.clone(), || Ok(cm)))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        PedersenGadget::<C, FV, ConstraintF>::enforce_equal(aggregation, commitments, tau, None)
+    }
+}
+
+#[derive(Clone)]
+struct EmbeddedCommitmentCircuit<P: TECurveConfig>
+where
+    P::BaseField: PrimeField,
+{
+    pub ck: Vec<TEAffine<P>>,
+
+    // public input
+    pub commitment: Option<TEAffine<P>>,
+
+    // witness
+    pub messages: Option<Vec<P::BaseField>>,
+}
+
+impl<P: TECurveConfig> ConstraintSynthesizer<P::BaseField> for EmbeddedCommitmentCircuit<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<P::BaseField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let commitment = AffineVar::<P, FpVar<P::BaseField>>::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let messages = Vec::<FpVar<P::BaseField>>::new_witness(cs.clone(), || {
+            self.messages.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        EmbeddedPedersenGadget::<P>::commit(&self.ck, &messages)?.enforce_equal(&commitment)
+    }
+}
+
+#[derive(Clone)]
+struct RegisteredBatchCircuit<C: CurveGroup, P: TECurveConfig<BaseField = C::ScalarField>>
+where
+    C::ScalarField: Absorb,
+{
+    pub ck: Vec<TEAffine<P>>,
+    pub params: PoseidonConfig<C::ScalarField>,
+
+    // public input
+    pub root: Option<C::ScalarField>,
+    pub tau: Option<C::ScalarField>,
+
+    // committed witness
+    pub aggregation: Option<Vec<C::ScalarField>>,
+    pub commitments: Option<Vec<Vec<C::ScalarField>>>,
+
+    // witness
+    pub proofs: Option<Vec<MembershipProof<C::ScalarField>>>,
+}
+
+impl<C, P> ConstraintSynthesizer<C::ScalarField> for RegisteredBatchCircuit<C, P>
+where
+    C: CurveGroup,
+    C::ScalarField: Absorb,
+    P: TECurveConfig<BaseField = C::ScalarField>,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<C::ScalarField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let root = FpVar::new_input(cs.clone(), || {
+            self.root.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let tau = FpVar::new_input(cs.clone(), || {
+            self.tau.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let aggregation = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.aggregation.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let commitments = self
+            .commitments
+            .ok_or(SynthesisError::AssignmentMissing)?
+            .into_iter()
+            .map(|cm| Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || Ok(cm)))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let proofs = self
+            .proofs
+            .ok_or(SynthesisError::AssignmentMissing)?
+            .into_iter()
+            .map(|proof| MembershipProofVar::new_witness(cs.clone(), || Ok(proof)))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        // every message of the batch is committed in the registry
+        for (cm, proof) in commitments.iter().zip(proofs.iter()) {
+            let registered = EmbeddedPedersenGadget::<P>::commit(&self.ck, cm)?;
+            RegistryGadget::<P>::enforce_membership(&self.params, &root, &registered, proof)?;
+        }
+
+        PedersenGadget::<C, FpVar<C::ScalarField>>::enforce_equal(
+            aggregation,
+            commitments,
+            tau,
+            None,
+        )
+    }
+}
+
+pub mod bn254 {
+    use super::*;
+    use crate::crypto::commitment::{
+        registry::{poseidon_config, Registry},
+        BatchCommitmentError, CommitmentScheme, HomomorphicCommitmentScheme, RegistryError,
+    };
+    use crate::crypto::protocol::transcript::{Context, Encoding};
+    use crate::solidity::Solidity;
+    use ark_ff::UniformRand;
+    use ark_r1cs_std::{fields::nonnative::NonNativeFieldVar, R1CSVar};
+    use ark_relations::r1cs::{ConstraintSystem, OptimizationGoal};
+    use ark_std::test_rng;
+
+    type C = ark_bn254::G1Projective;
+    type F = ark_bn254::Fr;
+    type BabyJubJub = ark_ed_on_bn254::EdwardsProjective;
+    type BabyJubJubConfig = ark_ed_on_bn254::EdwardsConfig;
+    type Secp256k1 = ark_secp256k1::Projective;
+    type Secp256k1Fr = ark_secp256k1::Fr;
+
+    fn count_constraints<F: PrimeField>(circuit: impl ConstraintSynthesizer<F>) -> usize {
+        let cs = ConstraintSystem::new_ref();
+        cs.set_optimization_goal(OptimizationGoal::Weight);
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        cs.finalize();
+        cs.num_constraints()
+    }
+
+    #[test]
+    fn homomorphic_update() {
+        let mut rng = test_rng();
+        let ck = (0..3)
+            .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+
+        // (balance, counter, opening)
+        let opening = vec![F::from(100u64), F::from(7u64), F::rand(&mut rng)];
+        let commitment = Pedersen::<C>::commit(&ck, &opening);
+
+        let mut delta = Pedersen::<C>::signed_delta(&[-30, 1]);
+        delta.push(F::rand(&mut rng));
+        let delta_commitment = Pedersen::<C>::commit(&ck, &delta);
+
+        let (updated, updated_opening) = Pedersen::<C>::update(&ck, &commitment, &opening, &delta);
+        assert_eq!(updated_opening[0], F::from(70u64));
+        assert_eq!(updated_opening[1], F::from(8u64));
+        assert_eq!(updated, Pedersen::<C>::commit(&ck, &updated_opening));
+        assert_eq!(updated, Pedersen::<C>::add(&commitment, &delta_commitment));
+        assert_eq!(
+            Pedersen::<C>::sub(&updated, &delta_commitment),
+            commitment,
+            "sub must undo add"
+        );
+        assert_eq!(
+            Pedersen::<C>::sub_openings(&updated_opening, &delta),
+            opening
+        );
+
+        // a shorter delta only touches the leading slots
+        let (partial, partial_opening) =
+            Pedersen::<C>::update(&ck, &commitment, &opening, &[F::from(5u64)]);
+        assert_eq!(partial_opening[0], F::from(105u64));
+        assert_eq!(partial_opening[2], opening[2]);
+        assert_eq!(partial, Pedersen::<C>::commit(&ck, &partial_opening));
+    }
+
+    #[test]
+    fn batch_challenge_context() {
+        let mut rng = test_rng();
+        let key = (0..2)
+            .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let messages = (0..3)
+            .map(|_| vec![F::rand(&mut rng), F::rand(&mut rng)])
+            .collect::<Vec<_>>();
+        let slices = messages.iter().map(|m| &m[..]).collect::<Vec<_>>();
+        let commitments = Pedersen::<C>::batch_commit(&key, &slices).unwrap();
+        let proof_dependent = <C as CurveGroup>::Affine::rand(&mut rng);
+        let public_inputs = [F::from(1u64)];
+
+        // abi.encodePacked(address, uint256, uint256)
+        let context = Context::new([0x11; 20], 1, 7);
+        let bytes = context.to_bytes(Encoding::Evm);
+        assert_eq!(bytes.len(), 20 + 32 + 32);
+        assert_eq!(bytes[51], 1);
+        assert_eq!(bytes[83], 7);
+
+        let challenge = |context: Option<&Context>| {
+            Pedersen::<C>::challenge_with_context(
+                &public_inputs,
+                &commitments,
+                &proof_dependent,
+                context,
+            )
+        };
+        assert_eq!(
+            challenge(None),
+            Pedersen::<C>::challenge(&public_inputs, &commitments, &proof_dependent)
+        );
+        assert_ne!(challenge(Some(&context)), challenge(None));
+
+        let replayed = Context::new([0x22; 20], 1, 7);
+        assert_ne!(challenge(Some(&context)), challenge(Some(&replayed)));
+        let next = Context::new([0x11; 20], 1, 8);
+        assert_ne!(challenge(Some(&context)), challenge(Some(&next)));
+    }
+
+    #[test]
+    fn homomorphic_update_circuit() {
+        let old = vec![F::from(100u64), F::from(7u64)];
+        let delta = Pedersen::<C>::signed_delta(&[-30, 1]);
+        let new = Pedersen::<C>::add_openings(&old, &delta);
+
+        let cs = ConstraintSystem::new_ref();
+        UpdateCircuit::<C> {
+            old: Some(old.clone()),
+            delta: Some(delta.clone()),
+            new: Some(new),
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        println!("update constraints: {}", cs.num_constraints());
+
+        let cs = ConstraintSystem::new_ref();
+        UpdateCircuit::<C> {
+            old: Some(old),
+            delta: Some(delta),
+            new: Some(vec![F::from(71u64), F::from(8u64)]),
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn embedded_pedersen_circuit() {
+        let mut rng = test_rng();
+        let ck = (0..3)
+            .map(|_| BabyJubJub::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        // messages are circuit (BN254 scalar field) elements
+        let messages = vec![F::from(100u64), F::from(7u64), F::rand(&mut rng)];
+        let commitment = Pedersen::<BabyJubJub>::commit_base_field(&ck, &messages);
+        println!("const cm = {:?}", commitment.to_solidity());
+
+        let cs = ConstraintSystem::new_ref();
+        EmbeddedCommitmentCircuit::<BabyJubJubConfig> {
+            ck: ck.clone(),
+            commitment: Some(commitment),
+            messages: Some(messages.clone()),
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        println!("embedded pedersen constraints: {}", cs.num_constraints());
+
+        let cs = ConstraintSystem::new_ref();
+        EmbeddedCommitmentCircuit::<BabyJubJubConfig> {
+            ck,
+            commitment: Some(commitment),
+            messages: Some(vec![F::from(101u64), messages[1], messages[2]]),
+        }
+        .generate_constraints(cs.clone())
+        .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn aggregation_circuit_native_and_emulated() {
+        let mut rng = test_rng();
+        println!("| batch | native | emulated (secp256k1) |");
+        println!("| --- | --- | --- |");
+        for batch_size in [1, 2, 4] {
+            // commit on BN254 G1, prove on BN254
+            let commitments = (0..batch_size)
+                .map(|_| vec![F::rand(&mut rng), F::rand(&mut rng)])
+                .collect::<Vec<_>>();
+            let native = AggregationCircuit::<C, F, FpVar<F>>::new(commitments, F::rand(&mut rng));
+
+            // commit on secp256k1, prove on BN254
+            let commitments = (0..batch_size)
+                .map(|_| vec![Secp256k1Fr::rand(&mut rng), Secp256k1Fr::rand(&mut rng)])
+                .collect::<Vec<_>>();
+            let emulated =
+                AggregationCircuit::<Secp256k1, F, NonNativeFieldVar<Secp256k1Fr, F>>::new(
+                    commitments,
+                    Secp256k1Fr::rand(&mut rng),
+                );
+
+            println!(
+                "| {} | {} | {} |",
+                batch_size,
+                count_constraints(native),
+                count_constraints(emulated)
+            );
+        }
+    }
+
+    #[test]
+    fn emulated_aggregation_circuit() {
+        let mut rng = test_rng();
+        type Emulated = AggregationCircuit<Secp256k1, F, NonNativeFieldVar<Secp256k1Fr, F>>;
+
+        // commit on secp256k1, prove on BN254
+        let commitments = (0..2)
+            .map(|_| vec![Secp256k1Fr::rand(&mut rng), Secp256k1Fr::rand(&mut rng)])
+            .collect::<Vec<_>>();
+        let circuit = Emulated::new(commitments, Secp256k1Fr::rand(&mut rng));
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        let mut tampered = circuit.clone();
+        tampered.aggregation.as_mut().unwrap()[0] += Secp256k1Fr::from(1u64);
+        let cs = ConstraintSystem::new_ref();
+        tampered.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // the aggregation is bound to the challenge it was computed with
+        let mut tampered = circuit;
+        tampered.tau = Some(Secp256k1Fr::rand(&mut rng));
+        let cs = ConstraintSystem::new_ref();
+        tampered.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // the emulated gadget computes the native aggregation, from `initial` on over a ragged batch
+        let commitments = vec![
+            vec![Secp256k1Fr::rand(&mut rng)],
+            vec![Secp256k1Fr::rand(&mut rng), Secp256k1Fr::rand(&mut rng)],
+            vec![Secp256k1Fr::rand(&mut rng)],
+        ];
+        let slices = commitments.iter().map(|cm| &cm[..]).collect::<Vec<_>>();
+        let (tau, initial) = (Secp256k1Fr::rand(&mut rng), Secp256k1Fr::rand(&mut rng));
+        let (aggregation, next) =
+            Pedersen::<Secp256k1>::scalar_aggregate(&slices, 2, tau, Some(initial)).unwrap();
+
+        type EmulatedVar = NonNativeFieldVar<Secp256k1Fr, F>;
+        let cs = ConstraintSystem::<F>::new_ref();
+        let commitments_var = commitments
+            .into_iter()
+            .map(|cm| Vec::<EmulatedVar>::new_witness(cs.clone(), || Ok(cm)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let tau_var = EmulatedVar::new_input(cs.clone(), || Ok(tau)).unwrap();
+        let initial_var = EmulatedVar::new_input(cs.clone(), || Ok(initial)).unwrap();
+        let (aggregation_var, next_var) = PedersenGadget::<Secp256k1, EmulatedVar, F>::aggregate(
+            commitments_var,
+            2,
+            tau_var,
+            Some(initial_var),
+        )
+        .unwrap();
+        assert_eq!(aggregation_var.value().unwrap(), aggregation);
+        assert_eq!(next_var.value().unwrap(), next);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn ragged_batch_aggregation() {
+        let mut rng = test_rng();
+        let batch_key = (0..3)
+            .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let tau = F::rand(&mut rng);
+
+        // messages of length 1, 3 and 2 under a batch key of width 3
+        let commitments = vec![
+            vec![F::rand(&mut rng)],
+            vec![F::rand(&mut rng), F::rand(&mut rng), F::rand(&mut rng)],
+            vec![F::rand(&mut rng), F::rand(&mut rng)],
+        ];
+        let slices: Vec<&[F]> = commitments.iter().map(|cm| &cm[..]).collect();
+
+        let commitments_g1 = Pedersen::<C>::batch_commit(&batch_key, &slices).unwrap();
+        let (aggregation_g1, next) = Pedersen::<C>::aggregate(&commitments_g1, tau, None).unwrap();
+        let (aggregation, _next) =
+            Pedersen::<C>::scalar_aggregate(&slices, batch_key.len(), tau, None).unwrap();
+        assert_eq!(aggregation.len(), batch_key.len());
+        assert_eq!(next, _next);
+        assert_eq!(
+            aggregation_g1,
+            Pedersen::<C>::commit(&batch_key, &aggregation)
+        );
+
+        // the aggregation is satisfied in-circuit over the ragged batch
+        let circuit = AggregationCircuit::<C, F, FpVar<F>>::new(commitments.clone(), tau);
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        let mut tampered = circuit;
+        tampered.aggregation.as_mut().unwrap()[2] += F::from(1u64);
+        let cs = ConstraintSystem::new_ref();
+        tampered.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // empty batches and oversized messages are rejected
+        assert_eq!(
+            Pedersen::<C>::scalar_aggregate(&[], 3, tau, None),
+            Err(BatchCommitmentError::EmptyBatch)
+        );
+        assert_eq!(
+            Pedersen::<C>::aggregate(&[], tau, None),
+            Err(BatchCommitmentError::EmptyBatch)
+        );
+        assert_eq!(
+            Pedersen::<C>::scalar_aggregate(&slices, 2, tau, None),
+            Err(BatchCommitmentError::Oversized {
+                index: 1,
+                len: 3,
+                width: 2
+            })
+        );
+        assert!(Pedersen::<C>::batch_commit(&batch_key[..2], &slices).is_err());
+    }
+
+    #[test]
+    fn registry_identity() {
+        let mut rng = test_rng();
+        let mut registry = Registry::<BabyJubJub>::new(poseidon_config(), 1).unwrap();
+        let identity = <BabyJubJub as CurveGroup>::Affine::zero();
+        let other = BabyJubJub::rand(&mut rng).into_affine();
+        registry.insert(&identity).unwrap();
+        registry.insert(&other).unwrap();
+        let root = registry.root();
+
+        // the identity has the same leaf natively and in-circuit
+        for (index, commitment, member) in [
+            (0, identity, true),
+            (1, identity, false),
+            (1, other, true),
+            (0, other, false),
+        ] {
+            let proof = registry.prove(index).unwrap();
+            assert_eq!(
+                Registry::<BabyJubJub>::verify(&registry.params, &root, &commitment, &proof),
+                member
+            );
+
+            let cs = ConstraintSystem::<F>::new_ref();
+            let root = FpVar::new_input(cs.clone(), || Ok(root)).unwrap();
+            let commitment =
+                AffineVar::<BabyJubJubConfig, FpVar<F>>::new_witness(cs.clone(), || Ok(commitment))
+                    .unwrap();
+            let proof = MembershipProofVar::new_witness(cs.clone(), || Ok(proof)).unwrap();
+            let is_member = RegistryGadget::<BabyJubJubConfig>::is_member(
+                &registry.params,
+                &root,
+                &commitment,
+                &proof,
+            )
+            .unwrap();
+            assert_eq!(is_member.value().unwrap(), member);
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn registry_membership() {
+        let mut rng = test_rng();
+        let ck = (0..2)
+            .map(|_| BabyJubJub::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        let mut registry = Registry::<BabyJubJub>::new(poseidon_config(), 2).unwrap();
+        let messages = (0..4)
+            .map(|_| vec![F::rand(&mut rng), F::rand(&mut rng)])
+            .collect::<Vec<_>>();
+        for (i, m) in messages.iter().enumerate() {
+            let cm = Pedersen::<BabyJubJub>::commit_base_field(&ck, m);
+            assert_eq!(registry.insert(&cm).unwrap(), i);
+        }
+        assert_eq!(
+            registry.insert(&ck[0]),
+            Err(RegistryError::Full { capacity: 4 })
+        );
+        assert!(registry.prove(4).is_err());
+
+        let root = registry.root();
+        let proof = registry.prove(2).unwrap();
+        let cm = *registry.get(2).unwrap();
+        assert!(Registry::<BabyJubJub>::verify(
+            &registry.params,
+            &root,
+            &cm,
+            &proof
+        ));
+        assert!(!Registry::<BabyJubJub>::verify(
+            &registry.params,
+            &root,
+            registry.get(1).unwrap(),
+            &proof
+        ));
+
+        // aggregate the registered messages of batch [1, 3]
+        let tau = F::rand(&mut rng);
+        let batch = vec![messages[1].clone(), messages[3].clone()];
+        let slices: Vec<&[F]> = batch.iter().map(|m| &m[..]).collect();
+        let (aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices, 2, tau, None).unwrap();
+        let circuit = RegisteredBatchCircuit::<C, BabyJubJubConfig> {
+            ck: ck.clone(),
+            params: registry.params.clone(),
+            root: Some(root),
+            tau: Some(tau),
+            aggregation: Some(aggregation),
+            commitments: Some(batch),
+            proofs: Some(vec![registry.prove(1).unwrap(), registry.prove(3).unwrap()]),
+        };
+        let cs = ConstraintSystem::new_ref();
+        circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        println!("registered batch constraints: {}", cs.num_constraints());
+
+        // an unregistered message cannot be aggregated
+        let mut unregistered = circuit;
+        let forged = vec![F::rand(&mut rng), F::rand(&mut rng)];
+        let slices: Vec<&[F]> = vec![&forged[..], &messages[3][..]];
+        let (aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices, 2, tau, None).unwrap();
+        unregistered.aggregation = Some(aggregation);
+        unregistered.commitments = Some(vec![forged, messages[3].clone()]);
+        let cs = ConstraintSystem::new_ref();
+        unregistered.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
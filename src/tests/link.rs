@@ -0,0 +1,779 @@
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, fields::fp::FpVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    One, UniformRand, Zero,
+};
+
+use super::{
+    circuit::{test_commitments, BatchCommitmentCircuit},
+    linker::LinkerCircuit,
+};
+
+use crate::{
+    crypto::{
+        commitment::{
+            pedersen::{Pedersen, PedersenGadget},
+            registry::{poseidon_config, Registry},
+            BatchCommitmentError, BatchCommitmentGadget, BatchCommitmentScheme,
+        },
+        protocol::transcript::{sha3::SHA3Base, Context, TranscriptProtocol},
+    },
+    gro::{self, CCGroth16},
+    link::{ChunkedProof, CpLink, Epoch, IncrementalProof, IncrementalState, LinkError},
+    linker::{am_com_eq::CommittingKey, Error},
+    snark::{CircuitSpecificSetupCCSNARK, CCSNARK},
+};
+
+/// Sum of the committed messages, as the public input.
+/// A circuit other than `LinkerCircuit` over the same committed witness.
+#[derive(Clone)]
+struct SumCircuit<C: CurveGroup> {
+    sum: Option<C::ScalarField>,
+    msg: Option<Vec<C::ScalarField>>,
+}
+
+impl<C: CurveGroup> SumCircuit<C> {
+    fn new(msg: Vec<C::ScalarField>) -> Self {
+        Self {
+            sum: Some(msg.iter().sum()),
+            msg: Some(msg),
+        }
+    }
+}
+
+impl<C: CurveGroup> ConstraintSynthesizer<C::ScalarField> for SumCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<C::ScalarField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let msg = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.msg.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let sum = FpVar::new_input(cs, || self.sum.ok_or(SynthesisError::AssignmentMissing))?;
+
+        msg.iter()
+            .sum::<FpVar<C::ScalarField>>()
+            .enforce_equal(&sum)
+    }
+}
+
+/// A segment of an incremental batch, aggregated from the power `initial` of `tau`
+#[derive(Clone)]
+struct SegmentCircuit<C: CurveGroup> {
+    tau: Option<C::ScalarField>,
+    initial: Option<C::ScalarField>,
+    aggregation: Option<Vec<C::ScalarField>>,
+    commitments: Option<Vec<Vec<C::ScalarField>>>,
+}
+
+impl<C: CurveGroup> SegmentCircuit<C> {
+    fn new(
+        commitments: Vec<Vec<C::ScalarField>>,
+        tau: C::ScalarField,
+        initial: C::ScalarField,
+    ) -> Self {
+        let slices = commitments.iter().map(|m| &m[..]).collect::<Vec<_>>();
+        let (aggregation, _) =
+            Pedersen::<C>::scalar_aggregate(&slices, 2, tau, Some(initial)).unwrap();
+
+        Self {
+            tau: Some(tau),
+            initial: Some(initial),
+            aggregation: Some(aggregation),
+            commitments: Some(commitments),
+        }
+    }
+
+    fn mock(len: usize) -> Self {
+        let zero = C::ScalarField::zero();
+        Self::new(vec![vec![zero; 2]; len], zero, zero)
+    }
+}
+
+impl<C: CurveGroup> ConstraintSynthesizer<C::ScalarField> for SegmentCircuit<C> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<C::ScalarField>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let tau = FpVar::new_input(cs.clone(), || {
+            self.tau.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let initial = FpVar::new_input(cs.clone(), || {
+            self.initial.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let aggregation = Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || {
+            self.aggregation.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let commitments = self
+            .commitments
+            .ok_or(SynthesisError::AssignmentMissing)?
+            .into_iter()
+            .map(|cm| Vec::<FpVar<C::ScalarField>>::new_witness(cs.clone(), || Ok(cm)))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        PedersenGadget::<C, FpVar<C::ScalarField>>::enforce_equal(
+            aggregation,
+            commitments,
+            tau,
+            Some(initial),
+        )
+    }
+}
+
+fn batch_link<E: Pairing, R: RngCore + CryptoRng>(batch_size: usize, rng: &mut R) {
+    let mock = BatchCommitmentCircuit::<E::G1>::mock(batch_size);
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 2, 2 + 2 * batch_size, rng).unwrap();
+    let pp = CpLink::<E>::batch_parameters(&ck).unwrap();
+
+    let context = Context::new([0x11; 20], 1, 0);
+    let commitments = test_commitments::<E::ScalarField>(batch_size, 2);
+    let committed_witness = commitments.concat();
+    let messages = commitments.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let state =
+        CpLink::<E>::commit_batch(&ck, &committed_witness, &messages, &[], Some(&context), rng)
+            .unwrap();
+
+    let circuit = BatchCommitmentCircuit::<E::G1>::new(commitments.clone(), state.instance.tau);
+    let proof = CpLink::<E>::prove_batch(
+        &pk,
+        &pp,
+        circuit,
+        &state,
+        Some(&context),
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+
+    let c_hat = &state.instance.c_hat;
+    let verify = |c_hat: &[E::G1Affine], proof, context: &Context| {
+        CpLink::<E>::verify_batch(
+            &vk,
+            &pp,
+            &[],
+            c_hat,
+            proof,
+            Some(context),
+            &mut SHA3Base::new(false),
+        )
+    };
+    assert_eq!(verify(c_hat, &proof, &context), Ok(()));
+
+    // tau is derived from the context and the batch, as the circuit is proved on it
+    let replayed = Context::new([0x11; 20], 1, 1);
+    assert_eq!(
+        verify(c_hat, &proof, &replayed),
+        Err(LinkError::InvalidSnark)
+    );
+    let mut swapped = c_hat.clone();
+    swapped.swap(0, 1);
+    assert_eq!(
+        verify(&swapped, &proof, &context),
+        Err(LinkError::InvalidSnark)
+    );
+    assert_eq!(
+        verify(&[], &proof, &context),
+        Err(LinkError::Commitment(BatchCommitmentError::EmptyBatch))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.snark.a = (tampered.snark.a + E::G1Affine::generator()).into_affine();
+    assert_eq!(
+        verify(c_hat, &tampered, &context),
+        Err(LinkError::InvalidSnark)
+    );
+    let mut tampered = proof.clone();
+    tampered.link.z[0] += E::ScalarField::one();
+    assert_eq!(
+        verify(c_hat, &tampered, &context),
+        Err(LinkError::InvalidLink)
+    );
+}
+
+fn external_link<E: Pairing, R: RngCore + CryptoRng>(l: usize, rng: &mut R) {
+    let mock = LinkerCircuit::<E::G1>::mock(l);
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 0, l, rng).unwrap();
+    let coeff_ck = CommittingKey {
+        g: vec![E::G1Affine::rand(rng)],
+        h: vec![E::G1Affine::rand(rng)],
+    };
+    let pp = CpLink::<E>::external_parameters(&ck, coeff_ck).unwrap();
+
+    let w = (0..l)
+        .map(|age| vec![E::ScalarField::from(age as u64 + 18)])
+        .collect::<Vec<_>>();
+    let beta = (0..l)
+        .map(|_| vec![E::ScalarField::rand(rng)])
+        .collect::<Vec<_>>();
+    let state = CpLink::<E>::commit_external(&ck, &pp, w, beta, rng).unwrap();
+
+    let circuit = LinkerCircuit::<E::G1>::new(state.witness.w.clone());
+    let proof = CpLink::<E>::prove_external(
+        &pk,
+        &pp,
+        circuit,
+        &state,
+        None,
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+
+    let c_hat = &state.instance.c_hat;
+    let verify = |c_hat: &[E::G1Affine], proof| {
+        CpLink::<E>::verify_external(&vk, &pp, &[], c_hat, proof, None, &mut SHA3Base::new(false))
+    };
+    assert_eq!(verify(c_hat, &proof), Ok(()));
+
+    // commitments to other messages
+    let mut other = c_hat.clone();
+    other[0] = (other[0] + pp.coeff_ck.g[0]).into_affine();
+    assert_eq!(verify(&other, &proof), Err(LinkError::InvalidLink));
+
+    let mut tampered = proof.clone();
+    tampered.snark.d = (tampered.snark.d + E::G1Affine::generator()).into_affine();
+    assert_eq!(verify(c_hat, &tampered), Err(LinkError::InvalidSnark));
+}
+
+fn dual_link<E: Pairing, R: RngCore + CryptoRng>(l: usize, rng: &mut R) {
+    let msg = (0..l)
+        .map(|age| E::ScalarField::from(age as u64 + 18))
+        .collect::<Vec<_>>();
+
+    let mock = LinkerCircuit::<E::G1>::mock(l);
+    let (pk1, vk1, ck1) = CCGroth16::<E>::setup(mock, 0, l, rng).unwrap();
+    let mock = SumCircuit::<E::G1>::new(msg.clone());
+    let (pk2, vk2, ck2) = CCGroth16::<E>::setup(mock, 0, l, rng).unwrap();
+    let pp = CpLink::<E>::dual_parameters(&ck1, &ck2).unwrap();
+
+    let state = CpLink::<E>::commit_dual(&ck1, &ck2, &msg, rng).unwrap();
+    let circuit1 = LinkerCircuit::<E::G1>::new(msg.iter().map(|m| vec![*m]).collect());
+    let circuit2 = SumCircuit::<E::G1>::new(msg.clone());
+    let proof = CpLink::<E>::prove_dual(
+        (&pk1, &pk2),
+        &pp,
+        (circuit1, circuit2),
+        &state,
+        None,
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+
+    let sum = [msg.iter().sum()];
+    let verify = |sum: &[E::ScalarField], proof| {
+        CpLink::<E>::verify_dual(
+            (&vk1, &vk2),
+            &pp,
+            (&[], sum),
+            proof,
+            None,
+            &mut SHA3Base::new(false),
+        )
+    };
+    assert_eq!(verify(&sum, &proof), Ok(()));
+    assert_eq!(
+        verify(&[sum[0] + E::ScalarField::one()], &proof),
+        Err(LinkError::InvalidSnark)
+    );
+
+    // the second circuit proved on other messages, with the same sum
+    let mut other = msg.clone();
+    other[0] += E::ScalarField::one();
+    other[1] -= E::ScalarField::one();
+    let commitment2 = CCGroth16::<E>::commit(&ck2, &other, rng).unwrap();
+    let mut tampered = proof.clone();
+    tampered.snark2 =
+        CCGroth16::<E>::prove(&pk2, SumCircuit::<E::G1>::new(other), &commitment2, rng).unwrap();
+    assert_eq!(verify(&sum, &tampered), Err(LinkError::InvalidLink));
+}
+
+/// Prove `segments` one after the other from `incremental.epoch`, returning their commitments
+fn extend_incremental<E: Pairing, R: RngCore + CryptoRng>(
+    pk: &gro::ProvingKey<E>,
+    ck: &gro::CommittingKey<E>,
+    segments: &[Vec<Vec<E::ScalarField>>],
+    incremental: &mut IncrementalState<E>,
+    context: &Context,
+    rng: &mut R,
+) -> Vec<E::G1Affine> {
+    let mut c_hat = vec![];
+    for segment in segments {
+        let messages = segment.iter().map(|m| &m[..]).collect::<Vec<_>>();
+        let state = CpLink::<E>::commit_segment(
+            ck,
+            &segment.concat(),
+            &messages,
+            &[],
+            &incremental.epoch,
+            Some(context),
+            rng,
+        )
+        .unwrap();
+        let tau = state.instance.tau;
+        let initial = incremental.epoch.initial(tau);
+        c_hat.extend(&state.instance.c_hat);
+
+        let circuit = SegmentCircuit::<E::G1>::new(segment.to_vec(), tau, initial);
+        CpLink::<E>::prove_segment(pk, circuit, state, incremental, rng).unwrap();
+    }
+    c_hat
+}
+
+fn incremental_link<E: Pairing, R: RngCore + CryptoRng>(
+    segment_len: usize,
+    num_segments: usize,
+    rng: &mut R,
+) {
+    let mock = SegmentCircuit::<E::G1>::mock(segment_len);
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 2, 2 + 2 * segment_len, rng).unwrap();
+    let pp = CpLink::<E>::batch_parameters(&ck).unwrap();
+
+    let context = Context::new([0x11; 20], 1, 0);
+    let link = |incremental: &IncrementalState<E>, rng: &mut R| {
+        CpLink::<E>::prove_incremental(
+            &pp,
+            incremental,
+            Some(&context),
+            &mut SHA3Base::new(false),
+            rng,
+        )
+        .unwrap()
+    };
+    let verify = |c_hat: &[E::G1Affine], proof: &IncrementalProof<E>| {
+        CpLink::<E>::verify_incremental(
+            &vk,
+            &pp,
+            &[],
+            c_hat,
+            proof,
+            Some(&context),
+            &mut SHA3Base::new(false),
+        )
+    };
+
+    // the batch grows one segment at a time, the previous segments are not proved again
+    let commitments = test_commitments::<E::ScalarField>(segment_len * num_segments, 2);
+    let segments = commitments
+        .chunks(segment_len)
+        .map(|segment| segment.to_vec())
+        .collect::<Vec<_>>();
+    let mut incremental = IncrementalState::<E>::new(Epoch::default());
+    let mut c_hat = vec![];
+    for segment in segments.chunks(1) {
+        c_hat.extend(extend_incremental(
+            &pk,
+            &ck,
+            segment,
+            &mut incremental,
+            &context,
+            rng,
+        ));
+        assert_eq!(verify(&c_hat, &link(&incremental, rng)), Ok(()));
+    }
+    let proof = link(&incremental, rng);
+    assert_eq!(proof.len(), commitments.len());
+
+    // the segments proved from where the first ones end are only linked once appended
+    // to those, into a single proof of the batch
+    let mut first = IncrementalState::<E>::new(Epoch::default());
+    let first_c_hat = extend_incremental(&pk, &ck, &segments[..1], &mut first, &context, rng);
+    assert_eq!(verify(&first_c_hat, &link(&first, rng)), Ok(()));
+    let mut rest = IncrementalState::<E>::new(first.epoch);
+    let rest_c_hat = extend_incremental(&pk, &ck, &segments[1..], &mut rest, &context, rng);
+    assert_eq!(
+        CpLink::<E>::prove_incremental(&pp, &rest, Some(&context), &mut SHA3Base::new(false), rng),
+        Err(LinkError::Linker(Error::MalformedProof))
+    );
+    assert_eq!(
+        IncrementalState::<E>::new(Epoch::default()).append(rest.clone()),
+        Err(LinkError::Linker(Error::MalformedProof))
+    );
+    first.append(rest).unwrap();
+    let combined = [first_c_hat, rest_c_hat].concat();
+    assert_eq!(verify(&combined, &link(&first, rng)), Ok(()));
+
+    // segments are chained to those before them, in order
+    let mut swapped = proof.clone();
+    swapped.segments.swap(0, 1);
+    assert_eq!(verify(&c_hat, &swapped), Err(LinkError::InvalidSnark));
+    let mut dropped = proof.clone();
+    dropped.segments.remove(0);
+    assert_eq!(
+        verify(&c_hat[segment_len..], &dropped),
+        Err(LinkError::InvalidSnark)
+    );
+
+    assert_eq!(
+        verify(&c_hat[1..], &proof),
+        Err(LinkError::Linker(Error::LengthMismatch {
+            expected: c_hat.len() - 1,
+            found: c_hat.len()
+        }))
+    );
+    let empty = IncrementalProof {
+        segments: vec![],
+        link: proof.link.clone(),
+    };
+    assert_eq!(
+        verify(&[], &empty),
+        Err(LinkError::Commitment(BatchCommitmentError::EmptyBatch))
+    );
+
+    // the linking step is over all the segments, and no other combination
+    let mut tampered = proof.clone();
+    tampered.link.z[0] += E::ScalarField::one();
+    assert_eq!(verify(&c_hat, &tampered), Err(LinkError::InvalidLink));
+    let mut replaced = proof.clone();
+    replaced.link = link(&first, rng).link;
+    assert_eq!(verify(&c_hat, &replaced), Err(LinkError::InvalidLink));
+}
+
+fn chunked_link<E: Pairing, R: RngCore + CryptoRng>(
+    chunk_len: usize,
+    num_chunks: usize,
+    rng: &mut R,
+) {
+    // the key is sized to one chunk, whatever the size of the batch
+    let mock = SegmentCircuit::<E::G1>::mock(chunk_len);
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 2, 2 + 2 * chunk_len, rng).unwrap();
+    let pp = CpLink::<E>::batch_parameters(&ck).unwrap();
+
+    let commitments = test_commitments::<E::ScalarField>(chunk_len * num_chunks, 2);
+    let messages = commitments.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let committed_witnesses = commitments
+        .chunks(chunk_len)
+        .map(|chunk| chunk.concat())
+        .collect::<Vec<_>>();
+    let context = Context::new([0x22; 20], 1, 0);
+    let state = CpLink::<E>::commit_chunked(
+        &ck,
+        &committed_witnesses,
+        &messages,
+        chunk_len,
+        &[],
+        Some(&context),
+        rng,
+    )
+    .unwrap();
+    let tau = state.instance.tau;
+
+    let circuits = commitments
+        .chunks(chunk_len)
+        .enumerate()
+        .map(|(i, chunk)| SegmentCircuit::<E::G1>::new(chunk.to_vec(), tau, state.initial(i)))
+        .collect::<Vec<_>>();
+    let prove = |circuits: Vec<SegmentCircuit<E::G1>>, rng: &mut R| {
+        CpLink::<E>::prove_chunked(
+            &pk,
+            &pp,
+            circuits,
+            &state,
+            Some(&context),
+            &mut SHA3Base::new(false),
+            rng,
+        )
+    };
+    let proof = prove(circuits.clone(), rng).unwrap();
+
+    // one circuit per chunk, checked before any of them is proved
+    let mut extra = circuits.clone();
+    extra.push(SegmentCircuit::<E::G1>::mock(chunk_len));
+    assert_eq!(
+        prove(extra, rng),
+        Err(LinkError::Linker(Error::LengthMismatch {
+            expected: num_chunks,
+            found: num_chunks + 1
+        }))
+    );
+
+    let c_hat = &state.instance.c_hat;
+    let verify = |c_hat: &[E::G1Affine], proof: &ChunkedProof<E>| {
+        CpLink::<E>::verify_chunked(
+            &vk,
+            &pp,
+            &[],
+            c_hat,
+            proof,
+            Some(&context),
+            &mut SHA3Base::new(false),
+        )
+    };
+    assert_eq!(verify(c_hat, &proof), Ok(()));
+
+    // the chunks are bound to their position in the batch
+    let mut swapped = proof.clone();
+    swapped.chunks.swap(0, 1);
+    assert_eq!(verify(c_hat, &swapped), Err(LinkError::InvalidSnark));
+
+    // a prover re-proving both chunks on messages moved from one to the next, compensated
+    // so as to keep the aggregate of the batch, fails on the aggregates of the slices of `c_hat`
+    let mut moved = commitments.clone();
+    let delta = E::ScalarField::from(7u64);
+    let shifted = delta * tau.pow([chunk_len as u64]);
+    moved[0].iter_mut().for_each(|m| *m += shifted);
+    moved[chunk_len].iter_mut().for_each(|m| *m -= delta);
+    let moved_witnesses = moved
+        .chunks(chunk_len)
+        .map(|chunk| chunk.concat())
+        .collect::<Vec<_>>();
+    let dishonest = CpLink::<E>::commit_chunked(
+        &ck,
+        &moved_witnesses,
+        &messages,
+        chunk_len,
+        &[],
+        Some(&context),
+        rng,
+    )
+    .unwrap();
+    let moved_circuits = moved
+        .chunks(chunk_len)
+        .enumerate()
+        .map(|(i, chunk)| {
+            SegmentCircuit::<E::G1>::new(
+                chunk.to_vec(),
+                dishonest.instance.tau,
+                dishonest.initial(i),
+            )
+        })
+        .collect::<Vec<_>>();
+    let moved = CpLink::<E>::prove_chunked(
+        &pk,
+        &pp,
+        moved_circuits,
+        &dishonest,
+        Some(&context),
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+    assert_eq!(verify(c_hat, &moved), Err(LinkError::InvalidSnark));
+
+    // the slices must sum up to the aggregate of the batch
+    let mut tampered = proof.clone();
+    let g = E::G1Affine::generator();
+    tampered.link.c = (tampered.link.c + g).into_affine();
+    assert_eq!(verify(c_hat, &tampered), Err(LinkError::InvalidLink));
+
+    // the length of the chunks is that of the batch over their number
+    assert_eq!(
+        verify(&c_hat[1..], &proof),
+        Err(LinkError::Linker(Error::LengthMismatch {
+            expected: (c_hat.len() - 1) / num_chunks * num_chunks,
+            found: c_hat.len() - 1
+        }))
+    );
+    assert_eq!(
+        CpLink::<E>::commit_chunked(
+            &ck,
+            &committed_witnesses,
+            &messages[1..],
+            chunk_len,
+            &[],
+            None,
+            rng
+        ),
+        Err(LinkError::Linker(Error::LengthMismatch {
+            expected: chunk_len * num_chunks,
+            found: c_hat.len() - 1
+        }))
+    );
+}
+
+fn migration_link<E: Pairing, R: RngCore + CryptoRng>(batch_size: usize, rng: &mut R)
+where
+    <E::G1 as CurveGroup>::BaseField: PrimeField + Absorb,
+{
+    // the same circuit, deployed again with a new setup
+    let mock = BatchCommitmentCircuit::<E::G1>::mock(batch_size);
+    let (_, _, old_ck) = CCGroth16::<E>::setup(mock.clone(), 2, 2 + 2 * batch_size, rng).unwrap();
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 2, 2 + 2 * batch_size, rng).unwrap();
+    let pp = CpLink::<E>::retarget_parameters(&old_ck, &ck).unwrap();
+
+    let commitments = test_commitments::<E::ScalarField>(batch_size, 2);
+    let messages = commitments.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let mut registry = Registry::<E::G1>::new(poseidon_config(), 2).unwrap();
+    for c in Pedersen::<E::G1>::batch_commit(&old_ck.batch_g1, &messages).unwrap() {
+        registry.insert(&c).unwrap();
+    }
+
+    let context = Context::new([0x33; 20], 1, 0);
+    let transcript = SHA3Base::new(false);
+    let migration = CpLink::<E>::migrate(
+        &pp,
+        &registry,
+        &commitments,
+        Some(&context),
+        &transcript,
+        rng,
+    )
+    .unwrap();
+    assert_eq!(migration.registry.capacity(), registry.capacity());
+    assert_eq!(
+        CpLink::<E>::verify_migration(
+            &pp,
+            &registry,
+            &migration.registry,
+            &migration.proofs,
+            Some(&context),
+            &transcript
+        ),
+        Ok(())
+    );
+
+    // the re-targeted commitments are those a batch under the new key is proved against
+    let c_hat = (0..batch_size)
+        .map(|i| *migration.registry.get(i).unwrap())
+        .collect::<Vec<_>>();
+    let pp_batch = CpLink::<E>::batch_parameters(&ck).unwrap();
+    let state =
+        CpLink::<E>::commit_batch(&ck, &commitments.concat(), &messages, &[], None, rng).unwrap();
+    assert_eq!(state.instance.c_hat, c_hat);
+    let circuit = BatchCommitmentCircuit::<E::G1>::new(commitments.clone(), state.instance.tau);
+    let proof = CpLink::<E>::prove_batch(
+        &pk,
+        &pp_batch,
+        circuit,
+        &state,
+        None,
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+    assert_eq!(
+        CpLink::<E>::verify_batch(
+            &vk,
+            &pp_batch,
+            &[],
+            &c_hat,
+            &proof,
+            None,
+            &mut SHA3Base::new(false)
+        ),
+        Ok(())
+    );
+
+    // a single commitment, re-targeted on its own
+    let (instance, proof) =
+        CpLink::<E>::retarget(&pp, &commitments[0], None, &mut SHA3Base::new(false), rng).unwrap();
+    assert_eq!(Some(&instance.d1), registry.get(0));
+    assert_eq!(
+        CpLink::<E>::verify_retarget(&pp, &instance, &proof, None, &mut SHA3Base::new(false)),
+        Ok(())
+    );
+    let mut other = instance.clone();
+    other.d2 = c_hat[1];
+    assert_eq!(
+        CpLink::<E>::verify_retarget(&pp, &other, &proof, None, &mut SHA3Base::new(false)),
+        Err(LinkError::InvalidLink)
+    );
+
+    // each commitment is re-targeted from the one at the same index
+    let mut swapped = migration.proofs.clone();
+    swapped.swap(0, 1);
+    assert_eq!(
+        CpLink::<E>::verify_migration(
+            &pp,
+            &registry,
+            &migration.registry,
+            &swapped,
+            Some(&context),
+            &transcript
+        ),
+        Err(LinkError::Linker(Error::InvalidProof(0)))
+    );
+
+    let mut wrong = commitments.clone();
+    wrong[1][0] += E::ScalarField::one();
+    assert!(matches!(
+        CpLink::<E>::migrate(&pp, &registry, &wrong, None, &transcript, rng),
+        Err(LinkError::InvalidOpening { index: 1 })
+    ));
+    assert!(matches!(
+        CpLink::<E>::migrate(&pp, &registry, &commitments[1..], None, &transcript, rng),
+        Err(LinkError::Linker(Error::LengthMismatch { .. }))
+    ));
+}
+
+pub mod bn254 {
+    use super::*;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        test_rng,
+    };
+
+    type E = ark_bn254::Bn254;
+    type R = StdRng;
+
+    #[test]
+    fn cp_link_batch() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        batch_link::<E, _>(4, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_external() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        external_link::<E, _>(4, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_dual() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        dual_link::<E, _>(4, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_incremental() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        incremental_link::<E, _>(2, 3, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_chunked() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        chunked_link::<E, _>(2, 3, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_migration() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        migration_link::<E, _>(4, &mut rng);
+    }
+
+    #[test]
+    fn cp_link_malformed_key() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        let mock = BatchCommitmentCircuit::<<E as Pairing>::G1>::mock(2);
+        let (_, _, mut ck) = CCGroth16::<E>::setup(mock, 2, 6, &mut rng).unwrap();
+
+        let empty = CommittingKey {
+            g: vec![],
+            h: vec![],
+        };
+        assert_eq!(
+            CpLink::<E>::external_parameters(&ck, empty),
+            Err(LinkError::MalformedKey)
+        );
+        let mut other = ck.clone();
+        other.proof_dependent_g1.pop();
+        assert_eq!(
+            CpLink::<E>::dual_parameters(&ck, &other),
+            Err(LinkError::MalformedKey)
+        );
+        ck.batch_g1.truncate(1);
+        assert_eq!(
+            CpLink::<E>::batch_parameters(&ck),
+            Err(LinkError::MalformedKey)
+        );
+        assert_eq!(
+            CpLink::<E>::retarget_parameters(&ck, &other),
+            Err(LinkError::MalformedKey)
+        );
+    }
+}
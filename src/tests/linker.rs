@@ -1,15 +1,19 @@
 use std::time::Instant;
 
+use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
 use ark_r1cs_std::{
     alloc::AllocVar,
     boolean::Boolean,
     eq::EqGadget,
     fields::{fp::FpVar, FieldVar},
-    ToBitsGadget,
+    uint8::UInt8,
+    R1CSVar, ToBitsGadget,
+};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
 };
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
 use ark_std::{
     rand::{CryptoRng, RngCore},
     vec::Vec,
@@ -21,19 +25,35 @@ use rayon::prelude::*;
 
 use crate::{
     crypto::{
-        commitment::{pedersen::Pedersen, CommitmentScheme},
+        commitment::{pedersen::Pedersen, BatchCommitmentScheme, CommitmentScheme},
         protocol::{
-            sigma::SigmaProtocol,
-            transcript::{sha3::SHA3Base, TranscriptProtocol},
+            channel::{run, run_alone, FiatShamir, MemoryChannel, Party},
+            sigma::{prove_with_phases, And, Either, Or, SigmaProtocol},
+            transcript::{
+                bind_context,
+                constraints::{PoseidonTranscriptVar, TranscriptGadget},
+                merlin::MerlinTranscript,
+                poseidon::PoseidonTranscript,
+                sha3::SHA3Base,
+                AppendToTranscript, Context, Encoding, TranscriptProtocol,
+            },
         },
     },
     gro::{CCGroth16, Commitment, ProvingKey},
     linker::{
         am_com_eq::{
+            self,
             data_structure::{CommittingKey, Instance, PublicParameters, Witness},
             AmComEq,
         },
-        comp_am_com_eq::CompAmComEq,
+        am_eq::{self, AmEq},
+        comp_am_com_eq::{self, CompAmComEq},
+        comp_am_eq::CompAmEq,
+        comp_dl_eq::{self, CompDLEq},
+        dual_eq::{self, DualEq},
+        pok::{self, Pok},
+        range::{self, RangeProof},
+        Error,
     },
     snark::{CircuitSpecificSetupCCSNARK, CCSNARK},
     solidity::Solidity,
@@ -42,7 +62,7 @@ use crate::{
 use super::utils::Average;
 
 #[derive(Clone)]
-struct LinkerCircuit<C: CurveGroup> {
+pub(super) struct LinkerCircuit<C: CurveGroup> {
     // committed witness
     msg: Option<Vec<C::ScalarField>>,
 }
@@ -102,24 +122,39 @@ fn linker_setup<C: CurveGroup, R: RngCore + CryptoRng>(
     let g_hat = vec![C::Affine::rand(rng); d0];
     let h_hat = vec![C::Affine::rand(rng); d2];
 
+    let pp = PublicParameters {
+        poly_ck: CommittingKey { g, h },
+        coeff_ck: CommittingKey { g: g_hat, h: h_hat },
+    };
+    let (instance, witness) = linker_instance(&pp, l, rng);
+
+    (pp, instance, witness)
+}
+
+/// Fresh instance of `l` commitments under the given public parameters
+fn linker_instance<C: CurveGroup, R: RngCore + CryptoRng>(
+    pp: &PublicParameters<C>,
+    l: usize,
+    rng: &mut R,
+) -> (Instance<C>, Witness<C>) {
+    let (g, h) = (&pp.poly_ck.g, &pp.poly_ck.h);
+    let (g_hat, h_hat) = (&pp.coeff_ck.g, &pp.coeff_ck.h);
+    let (d0, d1, d2) = (g_hat.len(), h.len(), h_hat.len());
+
     let w = vec![vec![C::ScalarField::rand(rng); d0]; l];
     let alpha = vec![C::ScalarField::rand(rng); d1];
     let beta = vec![vec![C::ScalarField::rand(rng); d2]; l];
 
     let w_flat = cfg_iter!(w).flat_map(|w_i| w_i.clone()).collect::<Vec<_>>();
-    let c = Pedersen::<C>::commit(&g, &w_flat) + Pedersen::<C>::commit(&h, &alpha);
+    let c = Pedersen::<C>::commit(g, &w_flat) + Pedersen::<C>::commit(h, &alpha);
     let c_hat = cfg_iter!(w)
         .zip(&beta)
         .map(|(w_i, beta_i)| {
-            Pedersen::<C>::commit(&g_hat, w_i) + Pedersen::<C>::commit(&h_hat, beta_i)
+            Pedersen::<C>::commit(g_hat, w_i) + Pedersen::<C>::commit(h_hat, beta_i)
         })
         .collect::<Vec<_>>();
 
     (
-        PublicParameters {
-            poly_ck: CommittingKey { g, h },
-            coeff_ck: CommittingKey { g: g_hat, h: h_hat },
-        },
         Instance {
             c: c.into_affine(),
             c_hat: C::normalize_batch(&c_hat),
@@ -158,6 +193,315 @@ fn process_linker<C: CurveGroup, R: RngCore + CryptoRng>(
     (prover.average(), verifier.average())
 }
 
+fn am_eq_setup<C: CurveGroup, R: RngCore + CryptoRng>(
+    d: usize,
+    rng: &mut R,
+) -> am_eq::PublicParameters<C> {
+    am_eq::PublicParameters {
+        poly_ck: CommittingKey {
+            g: (0..d).map(|_| C::Affine::rand(rng)).collect(),
+            h: vec![C::Affine::rand(rng)],
+        },
+        coeff_ck: CommittingKey {
+            g: (0..d).map(|_| C::Affine::rand(rng)).collect(),
+            h: vec![C::Affine::rand(rng)],
+        },
+    }
+}
+
+/// `l` commitments under `coeff_ck` whose aggregation with powers of `tau`
+/// opens to the same `(w, alpha)` as `c` under `poly_ck`
+fn am_eq_instance<C: CurveGroup, R: RngCore + CryptoRng>(
+    pp: &am_eq::PublicParameters<C>,
+    l: usize,
+    rng: &mut R,
+) -> (am_eq::Instance<C>, am_eq::Witness<C>) {
+    let d = pp.coeff_ck.g.len();
+    let width = d + pp.coeff_ck.h.len();
+    let coeff_key = [&pp.coeff_ck.g[..], &pp.coeff_ck.h].concat();
+
+    let messages = (0..l)
+        .map(|_| {
+            (0..width)
+                .map(|_| C::ScalarField::rand(rng))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let slices = messages.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let tau = C::ScalarField::rand(rng);
+    let (aggregation, _) = Pedersen::<C>::scalar_aggregate(&slices, width, tau, None).unwrap();
+
+    (
+        am_eq::Instance {
+            c_hat: Pedersen::<C>::batch_commit(&coeff_key, &slices).unwrap(),
+            tau,
+        },
+        am_eq::Witness {
+            w: aggregation[..d].to_vec(),
+            alpha: aggregation[d..].to_vec(),
+        },
+    )
+}
+
+fn process_comp_am_eq<C: CurveGroup, R: RngCore + CryptoRng>(
+    repeat: usize,
+    l: usize,
+    d: usize,
+    rng: &mut R,
+) -> (u128, u128)
+where
+    C::Affine: Solidity,
+    C::ScalarField: Solidity,
+{
+    let mut prover = vec![];
+    let mut verifier = vec![];
+    for _ in 0..repeat {
+        let pp = am_eq_setup::<C, _>(d, rng);
+        let (instance, witness) = am_eq_instance(&pp, l, rng);
+        let mut transcript = SHA3Base::new(true);
+
+        // prove
+        let prv_instant = Instant::now();
+        let proof = CompAmEq::<C>::prove(&pp, &instance, &witness, &mut transcript, rng)
+            .expect("proof failed");
+        prover.push(prv_instant.elapsed().as_micros());
+        drop(witness);
+
+        // verify
+        let vry_instant = Instant::now();
+        let mut transcript = SHA3Base::new(true);
+        assert!(CompAmEq::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+        verifier.push(vry_instant.elapsed().as_micros());
+
+        if repeat == 1 {
+            println!("const proof = {:?}", proof.to_solidity());
+        }
+    }
+    (prover.average(), verifier.average())
+}
+
+/// Check the honest-verifier zero-knowledge structure of `P` on a fixed challenge:
+/// both the honest and the simulated conversations are accepted,
+/// and a simulated conversation does not hold for another challenge
+fn check_conversations<P, R>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    rng: &mut R,
+) where
+    P: SigmaProtocol,
+    P::Challenge: UniformRand,
+    R: RngCore + CryptoRng,
+{
+    let mut transcript = SHA3Base::new(true);
+    let statement = P::bind(pp, instance, &mut transcript).unwrap();
+    let challenge = P::Challenge::rand(rng);
+
+    let (state, commitment) = P::commit(pp, &statement, witness, rng).unwrap();
+    let response = P::respond(pp, &statement, witness, state, &challenge).unwrap();
+    assert!(P::check(pp, &statement, &commitment, &challenge, &response).unwrap());
+
+    let (commitment, response) = P::simulate(pp, &statement, &challenge, rng).unwrap();
+    assert!(P::check(pp, &statement, &commitment, &challenge, &response).unwrap());
+
+    let other = P::Challenge::rand(rng);
+    assert!(!P::check(pp, &statement, &commitment, &other, &response).unwrap());
+}
+
+/// Proofs verify under the transcript they were made with, and not under the other one
+fn check_transcripts<P, R>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    rng: &mut R,
+) where
+    P: SigmaProtocol,
+    R: RngCore + CryptoRng,
+{
+    let mut transcript = SHA3Base::new(true);
+    let sha3_proof = P::prove(pp, instance, witness, &mut transcript, rng).expect("proof failed");
+    let mut transcript = MerlinTranscript::new(true);
+    let merlin_proof = P::prove(pp, instance, witness, &mut transcript, rng).expect("proof failed");
+
+    let mut transcript = SHA3Base::new(true);
+    assert!(P::verify(pp, instance, &sha3_proof, &mut transcript).unwrap());
+    let mut transcript = MerlinTranscript::new(true);
+    assert!(P::verify(pp, instance, &merlin_proof, &mut transcript).unwrap());
+
+    let mut transcript = MerlinTranscript::new(true);
+    assert!(!matches!(
+        P::verify(pp, instance, &sha3_proof, &mut transcript),
+        Ok(true)
+    ));
+    let mut transcript = SHA3Base::new(true);
+    assert!(!matches!(
+        P::verify(pp, instance, &merlin_proof, &mut transcript),
+        Ok(true)
+    ));
+}
+
+/// Proofs made with the canonical encoding verify with it, and not with the EVM encoding
+fn check_encodings<P, R>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    rng: &mut R,
+) where
+    P: SigmaProtocol,
+    R: RngCore + CryptoRng,
+{
+    let canonical = || SHA3Base::new(true).with_encoding(Encoding::Canonical);
+    let proof = P::prove(pp, instance, witness, &mut canonical(), rng).expect("proof failed");
+    assert!(P::verify(pp, instance, &proof, &mut canonical()).unwrap());
+
+    let mut transcript = SHA3Base::new(true);
+    assert!(!matches!(
+        P::verify(pp, instance, &proof, &mut transcript),
+        Ok(true)
+    ));
+}
+
+/// Proofs bound to a context verify under it only, and not without a context
+fn check_contexts<P, R>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    rng: &mut R,
+) where
+    P: SigmaProtocol,
+    R: RngCore + CryptoRng,
+{
+    let context = Context::new([0x11; 20], 1, 0);
+    let others = [
+        Some(Context::new([0x22; 20], 1, 0)),
+        Some(Context::new([0x11; 20], 10, 0)),
+        Some(Context::new([0x11; 20], 1, 1)),
+        None,
+    ];
+
+    let mut transcript = SHA3Base::new(true);
+    let proof = P::prove_with_context(pp, instance, witness, Some(&context), &mut transcript, rng)
+        .expect("proof failed");
+    let mut transcript = SHA3Base::new(true);
+    assert!(P::verify_with_context(pp, instance, &proof, Some(&context), &mut transcript).unwrap());
+
+    for other in others.iter() {
+        let mut transcript = SHA3Base::new(true);
+        assert!(!matches!(
+            P::verify_with_context(pp, instance, &proof, other.as_ref(), &mut transcript),
+            Ok(true)
+        ));
+    }
+}
+
+/// Poseidon transcript that keeps what it absorbs and squeezes, to be replayed in a circuit
+#[derive(Clone)]
+struct RecordingTranscript<F: PrimeField + Absorb> {
+    transcript: PoseidonTranscript<F>,
+    events: Vec<Event<F>>,
+}
+
+#[derive(Clone)]
+enum Event<F> {
+    Append(&'static [u8], Vec<u8>),
+    Challenge(&'static [u8], F),
+}
+
+impl<F: PrimeField + Absorb> TranscriptProtocol for RecordingTranscript<F> {
+    fn new(use_label: bool) -> Self {
+        Self {
+            transcript: PoseidonTranscript::new(use_label),
+            events: vec![],
+        }
+    }
+
+    fn append(&mut self, label: &'static [u8], item: &[u8]) {
+        self.transcript.append(label, item);
+        self.events.push(Event::Append(label, item.to_vec()));
+    }
+
+    fn challenge_scalar<G: PrimeField>(&mut self, label: &'static [u8]) -> G {
+        let challenge = self.transcript.challenge_native(label);
+        self.events.push(Event::Challenge(label, challenge));
+        G::from_le_bytes_mod_order(&challenge.into_bigint().to_bytes_le())
+    }
+}
+
+/// Proofs made with the Poseidon transcript verify with it, and the verifier's challenges
+/// are recomputed in a circuit from the same appended bytes
+fn replay_challenges<P, F, R>(
+    pp: &P::PublicParameters,
+    instance: &P::Instance,
+    witness: &P::Witness,
+    rng: &mut R,
+) where
+    P: SigmaProtocol,
+    F: PrimeField + Absorb,
+    R: RngCore + CryptoRng,
+{
+    let mut transcript = PoseidonTranscript::<F>::new(true);
+    let proof = P::prove(pp, instance, witness, &mut transcript, rng).expect("proof failed");
+    let mut transcript = RecordingTranscript::<F>::new(true);
+    assert!(P::verify(pp, instance, &proof, &mut transcript).unwrap());
+
+    let cs = ConstraintSystem::<F>::new_ref();
+    let mut gadget: PoseidonTranscriptVar<F> = TranscriptGadget::new(cs.clone(), true);
+    for event in transcript.events {
+        match event {
+            Event::Append(label, item) => {
+                let item = Vec::<UInt8<F>>::new_witness(cs.clone(), || Ok(item)).unwrap();
+                gadget.append(label, &item).unwrap();
+            }
+            Event::Challenge(label, challenge) => {
+                let challenge_var = gadget.challenge_scalar(label).unwrap();
+                assert_eq!(challenge_var.value().unwrap(), challenge);
+                challenge_var
+                    .enforce_equal(&FpVar::new_input(cs.clone(), || Ok(challenge)).unwrap())
+                    .unwrap();
+            }
+        }
+    }
+    assert!(cs.is_satisfied().unwrap());
+}
+
+/// Run both parties over an in-memory channel, with the prover or the verifier moving first
+fn run_interactive<P, V>(prover: &mut P, verifier: &mut V, prover_first: bool) -> bool
+where
+    P: Party<Output = (), Error = Error>,
+    V: Party<Output = bool, Error = Error>,
+{
+    let (mut prover_channel, mut verifier_channel) = MemoryChannel::pair();
+    let accepted = if prover_first {
+        run(prover, &mut prover_channel, verifier, &mut verifier_channel)
+            .expect("interaction failed")
+            .1
+    } else {
+        run(verifier, &mut verifier_channel, prover, &mut prover_channel)
+            .expect("interaction failed")
+            .0
+    };
+    assert_eq!(prover_channel.pending() + verifier_channel.pending(), 0);
+
+    accepted
+}
+
+/// Run the prover alone over `FiatShamir`, and the verifier alone over the resulting proof
+fn run_fiat_shamir<P, V>(prover: &mut P, verifier: &mut V) -> Result<bool, Error>
+where
+    P: Party<Output = (), Error = Error>,
+    V: Party<Output = bool, Error = Error>,
+{
+    let mut channel = FiatShamir::new(SHA3Base::new(true));
+    run_alone(prover, &mut channel).expect("proof failed");
+    let proof = channel.into_proof();
+
+    let mut channel = FiatShamir::with_proof(SHA3Base::new(true), proof);
+    let accepted = run_alone(verifier, &mut channel)?;
+    channel.finish()?;
+
+    Ok(accepted)
+}
+
 fn cp_link_setup<E: Pairing, R: RngCore + CryptoRng>(
     l: usize,
     rng: &mut R,
@@ -337,6 +681,22 @@ pub mod bn254 {
         }
     }
 
+    #[test]
+    fn comp_am_eq_scenario() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        for n in *LOG_MIN..=*LOG_MAX {
+            let l = 1 << n;
+
+            let (prv, vrf) = process_comp_am_eq::<C, R>(*NUM_REPEAT, l, l, &mut rng);
+            println!(
+                "Batch Size: 2^{} Prover: {} Verifier: {}",
+                n,
+                format_time(prv),
+                format_time(vrf)
+            );
+        }
+    }
+
     #[test]
     fn cp_link_scenario() {
         let mut rng = R::seed_from_u64(test_rng().next_u64());
@@ -386,4 +746,984 @@ pub mod bn254 {
             );
         }
     }
+
+    #[test]
+    fn invalid_proofs_are_rejected() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+
+        let mut transcript = SHA3Base::new(false);
+        let proof = AmComEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+
+        let mut tampered = proof.clone();
+        tampered.z[0] += <C as ark_ec::Group>::ScalarField::one();
+        let mut transcript = SHA3Base::new(false);
+        assert!(!AmComEq::<C>::verify(&pp, &instance, &tampered, &mut transcript).unwrap());
+
+        let mut truncated = proof;
+        truncated.z.pop();
+        let mut transcript = SHA3Base::new(false);
+        assert_eq!(
+            AmComEq::<C>::verify(&pp, &instance, &truncated, &mut transcript),
+            Err(Error::MalformedProof)
+        );
+
+        let mut transcript = SHA3Base::new(true);
+        let proof = CompAmComEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+
+        let mut tampered = proof.clone();
+        tampered.ace.z[1] += <C as ark_ec::Group>::ScalarField::one();
+        let mut transcript = SHA3Base::new(true);
+        assert!(!CompAmComEq::<C>::verify(&pp, &instance, &tampered, &mut transcript).unwrap());
+
+        let mut truncated = proof;
+        truncated.commitments.pop();
+        let mut transcript = SHA3Base::new(true);
+        assert_eq!(
+            CompAmComEq::<C>::verify(&pp, &instance, &truncated, &mut transcript),
+            Err(Error::MalformedProof)
+        );
+
+        // the identity cannot be absorbed into the transcript
+        let mut identity = instance;
+        identity.c = <C as CurveGroup>::Affine::zero();
+        let mut transcript = SHA3Base::new(false);
+        assert_eq!(
+            AmComEq::<C>::prove(&pp, &identity, &witness, &mut transcript, &mut rng),
+            Err(Error::IdentityPoint)
+        );
+    }
+
+    #[test]
+    fn batch_verification() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let (l, batch_size) = (1 << *LOG_MIN, 8);
+        let (pp, _, _) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+
+        let mut proofs = (0..batch_size)
+            .map(|_| {
+                let (instance, witness) = linker_instance(&pp, l, &mut rng);
+                let mut transcript = SHA3Base::new(false);
+                let proof =
+                    AmComEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+                        .expect("proof failed");
+                (instance, proof, SHA3Base::new(false))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(AmComEq::<C>::verify_batch(&pp, &proofs, &mut rng), Ok(()));
+
+        proofs[5].1.omega_hat[0] += F::one();
+        assert_eq!(
+            AmComEq::<C>::verify_batch(&pp, &proofs, &mut rng),
+            Err(Error::InvalidProof(5))
+        );
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let mut proofs = (0..batch_size)
+            .map(|_| {
+                let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+                let mut transcript = SHA3Base::new(false);
+                let proof = AmEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+                    .expect("proof failed");
+                (instance, proof, SHA3Base::new(false))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(AmEq::<C>::verify_batch(&pp, &proofs, &mut rng), Ok(()));
+
+        proofs[2].0.tau += F::one();
+        assert_eq!(
+            AmEq::<C>::verify_batch(&pp, &proofs, &mut rng),
+            Err(Error::InvalidProof(2))
+        );
+    }
+
+    #[test]
+    fn sigma_composition() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        let am_eq_pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (other_am_eq, _) = am_eq_instance(&am_eq_pp, l, &mut rng);
+        let (am_eq_instance, am_eq_witness) = am_eq_instance(&am_eq_pp, l, &mut rng);
+
+        // both statements under one transcript and one challenge
+        type Both = And<AmComEq<C>, AmEq<C>>;
+        let pp_and = (pp.clone(), am_eq_pp);
+        let instance_and = (instance.clone(), am_eq_instance);
+        let witness_and = (witness.clone(), am_eq_witness);
+
+        let mut transcript = SHA3Base::new(true);
+        let proof = Both::prove(
+            &pp_and,
+            &instance_and,
+            &witness_and,
+            &mut transcript,
+            &mut rng,
+        )
+        .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(Both::verify(&pp_and, &instance_and, &proof, &mut transcript).unwrap());
+
+        let mut tampered = proof;
+        tampered.1 .1.gamma[0] += F::one();
+        let mut transcript = SHA3Base::new(true);
+        assert!(!Both::verify(&pp_and, &instance_and, &tampered, &mut transcript).unwrap());
+
+        // the AmEq instance is absorbed, so another one gets another challenge
+        let challenges = [&instance_and.1, &other_am_eq].map(|instance| {
+            let mut transcript = SHA3Base::new(true);
+            AmEq::<C>::bind(&pp_and.1, instance, &mut transcript).unwrap();
+            AmEq::<C>::challenge(&mut transcript)
+        });
+        assert_ne!(challenges[0], challenges[1]);
+
+        // one of the two instances is ours, either one
+        type OneOf = Or<AmComEq<C>, AmComEq<C>>;
+        let (other, _) = linker_instance(&pp, l, &mut rng);
+        let pp_or = (pp.clone(), pp.clone());
+
+        for (instance_or, witness_or) in [
+            (
+                (instance.clone(), other.clone()),
+                Either::Left(witness.clone()),
+            ),
+            (
+                (other.clone(), instance.clone()),
+                Either::Right(witness.clone()),
+            ),
+        ] {
+            let mut transcript = SHA3Base::new(true);
+            let proof = OneOf::prove(&pp_or, &instance_or, &witness_or, &mut transcript, &mut rng)
+                .expect("proof failed");
+            let mut transcript = SHA3Base::new(true);
+            assert!(OneOf::verify(&pp_or, &instance_or, &proof, &mut transcript).unwrap());
+
+            // the challenges no longer sum up to the one of the transcript
+            let mut tampered = proof;
+            tampered.1 .0 += F::one();
+            let mut transcript = SHA3Base::new(true);
+            assert!(!OneOf::verify(&pp_or, &instance_or, &tampered, &mut transcript).unwrap());
+        }
+
+        // a witness for neither instance
+        let instance_or = (other.clone(), other);
+        let mut transcript = SHA3Base::new(true);
+        let proof = OneOf::prove(
+            &pp_or,
+            &instance_or,
+            &Either::Left(witness),
+            &mut transcript,
+            &mut rng,
+        )
+        .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(!OneOf::verify(&pp_or, &instance_or, &proof, &mut transcript).unwrap());
+    }
+
+    #[test]
+    fn honest_verifier_zero_knowledge() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        check_conversations::<AmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_conversations::<CompAmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        // `prove` is the Fiat-Shamir transform of the three moves
+        let mut prover_rng = rng.clone();
+        let mut transcript = SHA3Base::new(true);
+        let proof = AmComEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        let (commitment, response) = prove_with_phases::<AmComEq<C>, _, _>(
+            &pp,
+            &instance,
+            &witness,
+            &mut transcript,
+            &mut prover_rng,
+        )
+        .expect("proof failed");
+        assert_eq!(proof.commitment, commitment);
+        assert_eq!(proof.z, response.z);
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        check_conversations::<AmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_conversations::<CompAmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let n = l * *D0;
+        let pp = CompDLEq::<C>::setup(&comp_dl_eq::PublicParameters {
+            g: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+            g_hat: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        })
+        .unwrap();
+        let witness = comp_dl_eq::Witness::<C> {
+            z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = CompDLEq::<C>::evaluate(&pp, &witness.z).unwrap();
+        check_conversations::<CompDLEq<C>, _>(&pp, &instance, &witness, &mut rng);
+    }
+
+    #[test]
+    fn proof_of_opening() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let (width, batch_size) = (*D0 + 1, 8);
+        let pp = pok::PublicParameters::<C> {
+            ck: (0..width)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+
+        // ragged openings, committed as in a batch
+        let openings = (0..batch_size)
+            .map(|i| {
+                (0..1 + i % width)
+                    .map(|_| F::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let slices = openings.iter().map(|m| &m[..]).collect::<Vec<_>>();
+        let commitments = Pedersen::<C>::batch_commit(&pp.ck, &slices).unwrap();
+
+        let mut proofs = commitments
+            .iter()
+            .zip(openings)
+            .map(|(commitment, opening)| {
+                let instance = pok::Instance {
+                    commitment: *commitment,
+                };
+                let witness = pok::Witness { opening };
+                let mut transcript = SHA3Base::new(true);
+                let proof = Pok::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+                    .expect("proof failed");
+
+                let mut transcript = SHA3Base::new(true);
+                assert!(Pok::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+                assert_eq!(
+                    proof.to_solidity().len(),
+                    width + proof.a.to_solidity().len()
+                );
+                check_conversations::<Pok<C>, _>(&pp, &instance, &witness, &mut rng);
+
+                (instance, proof, SHA3Base::new(true))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Pok::<C>::verify_batch(&pp, &proofs, &mut rng), Ok(()));
+
+        proofs[3].1.z[0] += F::one();
+        assert_eq!(
+            Pok::<C>::verify_batch(&pp, &proofs, &mut rng),
+            Err(Error::InvalidProof(3))
+        );
+
+        // an opening of another commitment
+        let instance = pok::Instance {
+            commitment: commitments[0],
+        };
+        let witness = pok::Witness {
+            opening: vec![F::rand(&mut rng); width],
+        };
+        let mut transcript = SHA3Base::new(true);
+        let proof = Pok::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(!Pok::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+
+        let witness = pok::Witness {
+            opening: vec![F::rand(&mut rng); width + 1],
+        };
+        let mut transcript = SHA3Base::new(true);
+        assert_eq!(
+            Pok::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng),
+            Err(Error::LengthMismatch {
+                expected: width,
+                found: width + 1
+            })
+        );
+    }
+
+    #[test]
+    fn dual_equality() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let n = 1 << *LOG_MIN;
+        let mut key = |d: usize| CommittingKey::<C> {
+            g: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+            h: (0..d)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+        let pp = dual_eq::PublicParameters {
+            ck1: key(1),
+            ck2: key(*D2),
+        };
+
+        let commit = |ck: &CommittingKey<C>, w: &[F], alpha: &[F]| {
+            (Pedersen::<C>::commit(&ck.g, w) + Pedersen::<C>::commit(&ck.h, alpha)).into_affine()
+        };
+        let w = (0..n).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+        let alpha1 = vec![F::rand(&mut rng)];
+        let alpha2 = (0..*D2).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+        let instance = dual_eq::Instance {
+            d1: commit(&pp.ck1, &w, &alpha1),
+            d2: commit(&pp.ck2, &w, &alpha2),
+        };
+        let witness = dual_eq::Witness { w, alpha1, alpha2 };
+
+        let mut transcript = SHA3Base::new(true);
+        let proof = DualEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(DualEq::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+        check_conversations::<DualEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_transcripts::<DualEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_encodings::<DualEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_contexts::<DualEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        // d2 commits to other messages
+        let mut other = witness.w.clone();
+        other[0] += F::one();
+        let instance = dual_eq::Instance {
+            d2: commit(&pp.ck2, &other, &witness.alpha2),
+            ..instance
+        };
+        let mut transcript = SHA3Base::new(true);
+        let proof = DualEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(!DualEq::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+
+        let mut transcript = SHA3Base::new(true);
+        let short = dual_eq::Witness {
+            w: other[1..].to_vec(),
+            ..witness
+        };
+        assert_eq!(
+            DualEq::<C>::prove(&pp, &instance, &short, &mut transcript, &mut rng),
+            Err(Error::LengthMismatch {
+                expected: n,
+                found: n - 1
+            })
+        );
+    }
+
+    #[test]
+    fn range_proof() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let g = <C as CurveGroup>::Affine::rand(&mut rng);
+        let h = <C as CurveGroup>::Affine::rand(&mut rng);
+        let pp = RangeProof::<C>::generate_public_parameters(g, h, 64, &mut rng);
+
+        for (bits, values) in [
+            (8, vec![255]),
+            (64, vec![u64::MAX]),
+            (16, vec![0, 1, 42, (1 << 16) - 1]),
+            (1, vec![0, 1]),
+        ] {
+            let gamma = (0..values.len()).map(|_| F::rand(&mut rng)).collect();
+            let witness = range::Witness::<C> { values, gamma };
+            let instance = range::Instance {
+                commitments: witness
+                    .values
+                    .iter()
+                    .zip(&witness.gamma)
+                    .map(|(v, gamma)| RangeProof::commit(&pp, *v, *gamma))
+                    .collect(),
+                bits,
+            };
+            assert_eq!(
+                instance.commitments[0],
+                Pedersen::<C>::commit(&[g, h], &[F::from(witness.values[0]), witness.gamma[0]])
+            );
+
+            let mut transcript = SHA3Base::new(true);
+            let proof =
+                RangeProof::create_proof(&pp, &instance, &witness, &mut transcript, &mut rng)
+                    .expect("proof failed");
+            let mut transcript = SHA3Base::new(true);
+            assert!(RangeProof::verify_proof(&pp, &instance, &proof, &mut transcript).unwrap());
+
+            let mut tampered = proof.clone();
+            tampered.t_hat += F::one();
+            let mut transcript = SHA3Base::new(true);
+            assert!(!RangeProof::verify_proof(&pp, &instance, &tampered, &mut transcript).unwrap());
+
+            let mut tampered = proof;
+            tampered.ipa.a[0] += F::one();
+            let mut transcript = SHA3Base::new(true);
+            assert!(!RangeProof::verify_proof(&pp, &instance, &tampered, &mut transcript).unwrap());
+        }
+
+        // the prover refuses values out of range
+        let witness = range::Witness::<C> {
+            values: vec![1 << 8],
+            gamma: vec![F::rand(&mut rng)],
+        };
+        let instance = range::Instance {
+            commitments: vec![RangeProof::commit(&pp, 1 << 8, witness.gamma[0])],
+            bits: 8,
+        };
+        let mut transcript = SHA3Base::new(true);
+        assert_eq!(
+            RangeProof::create_proof(&pp, &instance, &witness, &mut transcript, &mut rng),
+            Err(Error::OutOfRange(0))
+        );
+
+        let instance = range::Instance {
+            bits: 12,
+            ..instance
+        };
+        let mut transcript = SHA3Base::new(true);
+        assert_eq!(
+            RangeProof::create_proof(&pp, &instance, &witness, &mut transcript, &mut rng),
+            Err(Error::InvalidBitLength(12))
+        );
+
+        // a single bit leaves nothing for the inner-product argument to fold
+        let witness = range::Witness::<C> {
+            values: vec![1],
+            gamma: vec![F::rand(&mut rng)],
+        };
+        let instance = range::Instance {
+            commitments: vec![RangeProof::commit(&pp, 1, witness.gamma[0])],
+            bits: 1,
+        };
+        let mut transcript = SHA3Base::new(true);
+        assert_eq!(
+            RangeProof::create_proof(&pp, &instance, &witness, &mut transcript, &mut rng),
+            Err(Error::InvalidBitLength(1))
+        );
+    }
+
+    #[test]
+    fn multi_base_comp_dl_eq() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let (k, n) = (3, 16);
+        let pp = comp_dl_eq::MultiPublicParameters::<C> {
+            bases: (0..k)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                        .collect()
+                })
+                .collect(),
+        };
+        let witness = comp_dl_eq::Witness::<C> {
+            z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = comp_dl_eq::MultiInstance::<C> {
+            y: pp
+                .bases
+                .iter()
+                .map(|g| Pedersen::<C>::commit(g, &witness.z))
+                .collect(),
+        };
+
+        for threshold in [1, 2, 4, n] {
+            let mut transcript = SHA3Base::new(true);
+            let proof =
+                CompDLEq::create_multi_proof(&pp, &instance, &witness, threshold, &mut transcript)
+                    .expect("proof failed");
+            assert_eq!(proof.z.len(), threshold);
+            assert_eq!(
+                proof.commitments.len(),
+                (n / threshold).trailing_zeros() as usize
+            );
+            let mut transcript = SHA3Base::new(true);
+            assert!(CompDLEq::verify_multi_proof(&pp, &instance, &proof, &mut transcript).unwrap());
+
+            let mut tampered = proof.clone();
+            tampered.z[0] += F::one();
+            let mut transcript = SHA3Base::new(true);
+            assert!(
+                !CompDLEq::verify_multi_proof(&pp, &instance, &tampered, &mut transcript).unwrap()
+            );
+
+            let mut tampered = proof;
+            tampered.z.pop();
+            let mut transcript = SHA3Base::new(true);
+            assert_eq!(
+                CompDLEq::verify_multi_proof(&pp, &instance, &tampered, &mut transcript),
+                Err(Error::MalformedProof)
+            );
+        }
+
+        // the two-base API is the multi-base protocol over `[g, g_hat]`
+        let two_base = comp_dl_eq::PublicParameters::<C> {
+            g: pp.bases[0].clone(),
+            g_hat: pp.bases[1].clone(),
+        };
+        let two_instance = CompDLEq::<C>::evaluate(&two_base, &witness.z).unwrap();
+        let mut transcript = SHA3Base::new(true);
+        let proof = CompDLEq::create_proof(&two_base, &two_instance, &witness, &mut transcript)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        let multi_proof = CompDLEq::create_multi_proof(
+            &(&two_base).into(),
+            &(&two_instance).into(),
+            &witness,
+            2,
+            &mut transcript,
+        )
+        .expect("proof failed");
+        assert_eq!(comp_dl_eq::MultiProof::from(&proof), multi_proof);
+
+        let mut transcript = SHA3Base::new(true);
+        let proof = CompDLEq::create_proof_with_threshold(
+            &two_base,
+            &two_instance,
+            &witness,
+            1,
+            &mut transcript,
+        )
+        .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(CompDLEq::verify_proof(&two_base, &two_instance, &proof, &mut transcript).unwrap());
+    }
+
+    #[test]
+    fn odd_length_comp_dl_eq() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        for n in [1, 3, 5, 6, 13, 100] {
+            let pp = CompDLEq::<C>::setup(&comp_dl_eq::PublicParameters {
+                g: (0..n)
+                    .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                    .collect(),
+                g_hat: (0..n)
+                    .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                    .collect(),
+            })
+            .unwrap();
+            // no padding, the generators are used as they are
+            assert_eq!(pp.g.len(), n);
+            let witness = comp_dl_eq::Witness::<C> {
+                z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+            };
+            let instance = CompDLEq::<C>::evaluate(&pp, &witness.z).unwrap();
+
+            for threshold in [1, 2, 3] {
+                let mut transcript = SHA3Base::new(true);
+                let proof = CompDLEq::create_proof_with_threshold(
+                    &pp,
+                    &instance,
+                    &witness,
+                    threshold,
+                    &mut transcript,
+                )
+                .expect("proof failed");
+                assert!(proof.z.len() <= threshold);
+                assert_eq!(
+                    CompDLEq::<C>::folding_rounds(n, proof.z.len()),
+                    Some(proof.commitments.len())
+                );
+                let mut transcript = SHA3Base::new(true);
+                assert!(CompDLEq::verify_proof(&pp, &instance, &proof, &mut transcript).unwrap());
+
+                // the last element is the one carried forward on odd lengths
+                let mut tampered = proof;
+                let last = tampered.z.len() - 1;
+                tampered.z[last] += F::one();
+                let mut transcript = SHA3Base::new(true);
+                assert!(
+                    !CompDLEq::verify_proof(&pp, &instance, &tampered, &mut transcript).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn interactive_linkers() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+        // prover and verifier draw from their own rngs
+        let (prover_seed, verifier_seed) = (rng.next_u64(), rng.next_u64());
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        let prover = || {
+            am_com_eq::InteractiveProver::new(
+                &pp,
+                &instance,
+                &witness,
+                R::seed_from_u64(prover_seed),
+            )
+        };
+        let verifier =
+            || am_com_eq::InteractiveVerifier::new(&pp, &instance, R::seed_from_u64(verifier_seed));
+        assert!(run_interactive(&mut prover(), &mut verifier(), false));
+        assert!(run_fiat_shamir(&mut prover(), &mut verifier()).unwrap());
+
+        // the witness does not open the instance of the verifier
+        let (other, _) = linker_instance(&pp, l, &mut rng);
+        let mut verifier =
+            am_com_eq::InteractiveVerifier::new(&pp, &other, R::seed_from_u64(verifier_seed));
+        assert!(!run_interactive(&mut prover(), &mut verifier, false));
+
+        let prover = || {
+            comp_am_com_eq::InteractiveProver::new(
+                &pp,
+                &instance,
+                &witness,
+                R::seed_from_u64(prover_seed),
+            )
+        };
+        let verifier = || {
+            comp_am_com_eq::InteractiveVerifier::new(
+                &pp,
+                &instance,
+                R::seed_from_u64(verifier_seed),
+            )
+        };
+        assert!(run_interactive(&mut prover(), &mut verifier(), false));
+        assert!(run_fiat_shamir(&mut prover(), &mut verifier()).unwrap());
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        let prover = || {
+            am_eq::InteractiveProver::new(
+                &pp,
+                instance.clone(),
+                &witness,
+                R::seed_from_u64(prover_seed),
+            )
+        };
+        let verifier = || {
+            am_eq::InteractiveVerifier::new(&pp, instance.clone(), R::seed_from_u64(verifier_seed))
+        };
+        assert!(run_interactive(&mut prover(), &mut verifier(), true));
+        assert!(run_fiat_shamir(&mut prover(), &mut verifier()).unwrap());
+
+        let (k, n, threshold) = (3, 13, 2);
+        let pp = comp_dl_eq::MultiPublicParameters::<C> {
+            bases: (0..k)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                        .collect()
+                })
+                .collect(),
+        };
+        let witness = comp_dl_eq::Witness::<C> {
+            z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = comp_dl_eq::MultiInstance::<C> {
+            y: pp
+                .bases
+                .iter()
+                .map(|g| Pedersen::<C>::commit(g, &witness.z))
+                .collect(),
+        };
+        let prover = || {
+            comp_dl_eq::InteractiveProver::new(
+                pp.clone(),
+                instance.clone(),
+                witness.clone(),
+                threshold,
+            )
+            .unwrap()
+        };
+        let verifier = |instance: &comp_dl_eq::MultiInstance<C>| {
+            comp_dl_eq::InteractiveVerifier::new(
+                pp.clone(),
+                instance.clone(),
+                threshold,
+                R::seed_from_u64(verifier_seed),
+            )
+            .unwrap()
+        };
+        assert!(run_interactive(
+            &mut prover(),
+            &mut verifier(&instance),
+            true
+        ));
+        assert!(run_fiat_shamir(&mut prover(), &mut verifier(&instance)).unwrap());
+
+        let mut other = instance.clone();
+        other.y.swap(0, 1);
+        assert!(!run_interactive(&mut prover(), &mut verifier(&other), true));
+        assert!(!run_fiat_shamir(&mut prover(), &mut verifier(&other)).unwrap());
+    }
+
+    #[test]
+    fn poseidon_transcript() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        // x of compute_powers_of_x, then e of compute_e
+        replay_challenges::<AmComEq<C>, F, _>(&pp, &instance, &witness, &mut rng);
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        replay_challenges::<AmEq<C>, F, _>(&pp, &instance, &witness, &mut rng);
+    }
+
+    #[test]
+    fn poseidon_gadget_challenges() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        let mut transcript = PoseidonTranscript::<F>::new(true);
+        let proof = AmComEq::<C>::prove(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+
+        let mut transcript = PoseidonTranscript::<F>::new(true);
+        let powers_of_x = AmComEq::<C>::compute_powers_of_x(&instance, &mut transcript).unwrap();
+        let e = AmComEq::<C>::compute_e(&proof.commitment, &mut transcript).unwrap();
+        let mut transcript = PoseidonTranscript::<F>::new(true);
+        assert!(AmComEq::<C>::verify(&pp, &instance, &proof, &mut transcript).unwrap());
+
+        // the instance and the commitment appended in the circuit as the bytes of their encoding
+        let cs = ConstraintSystem::<F>::new_ref();
+        let mut gadget: PoseidonTranscriptVar<F> = TranscriptGadget::new(cs.clone(), true);
+        let encoding = PoseidonTranscript::<F>::new(true).encoding();
+        let mut bytes = vec![];
+        instance.encode(encoding, &mut bytes).unwrap();
+        let bytes = Vec::<UInt8<F>>::new_witness(cs.clone(), || Ok(bytes)).unwrap();
+        gadget.append(b"instance", &bytes).unwrap();
+        let x = gadget.challenge_scalar(b"challenge").unwrap();
+        let mut power = FpVar::one();
+        for expected in &powers_of_x {
+            assert_eq!(power.value().unwrap(), *expected);
+            power *= &x;
+        }
+        let mut bytes = vec![];
+        proof.commitment.encode(encoding, &mut bytes).unwrap();
+        let bytes = Vec::<UInt8<F>>::new_witness(cs.clone(), || Ok(bytes)).unwrap();
+        gadget.append(b"commitment", &bytes).unwrap();
+        let e_var = gadget.challenge_scalar(b"challenge").unwrap();
+        assert_eq!(e_var.value().unwrap(), e);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn canonical_encoding() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        check_encodings::<AmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_encodings::<CompAmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        // the identity is encoded, and the proof fails on the equation instead
+        let mut identity = instance;
+        identity.c = <C as CurveGroup>::Affine::zero();
+        let mut transcript = SHA3Base::new(true).with_encoding(Encoding::Canonical);
+        let proof = AmComEq::<C>::prove(&pp, &identity, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = SHA3Base::new(true).with_encoding(Encoding::Canonical);
+        assert!(!AmComEq::<C>::verify(&pp, &identity, &proof, &mut transcript).unwrap());
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        check_encodings::<AmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let n = 7;
+        let pp = comp_dl_eq::PublicParameters::<C> {
+            g: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+            g_hat: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+        let witness = comp_dl_eq::Witness::<C> {
+            z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = CompDLEq::<C>::evaluate(&pp, &witness.z).unwrap();
+        check_encodings::<CompDLEq<C>, _>(&pp, &instance, &witness, &mut rng);
+    }
+
+    #[test]
+    fn context_binding() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        check_contexts::<AmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_contexts::<CompAmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        // batch verification takes the transcripts bound to the context of each proof
+        let contexts = (0..4)
+            .map(|nonce| Context::new([0x11; 20], 1, nonce))
+            .collect::<Vec<_>>();
+        let mut proofs = contexts
+            .iter()
+            .map(|context| {
+                let (instance, witness) = linker_instance(&pp, l, &mut rng);
+                let mut transcript = SHA3Base::new(false);
+                let proof = AmComEq::<C>::prove_with_context(
+                    &pp,
+                    &instance,
+                    &witness,
+                    Some(context),
+                    &mut transcript,
+                    &mut rng,
+                )
+                .expect("proof failed");
+                let mut transcript = SHA3Base::new(false);
+                bind_context(Some(context), &mut transcript);
+                (instance, proof, transcript)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(AmComEq::<C>::verify_batch(&pp, &proofs, &mut rng), Ok(()));
+
+        // a proof replayed with the nonce of another
+        proofs[1].2 = SHA3Base::new(false);
+        bind_context(Some(&contexts[2]), &mut proofs[1].2);
+        assert_eq!(
+            AmComEq::<C>::verify_batch(&pp, &proofs, &mut rng),
+            Err(Error::InvalidProof(1))
+        );
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        check_contexts::<AmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_contexts::<CompAmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let pp = pok::PublicParameters::<C> {
+            ck: (0..*D0)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+        let witness = pok::Witness {
+            opening: (0..*D0).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = pok::Instance {
+            commitment: Pedersen::<C>::commit(&pp.ck, &witness.opening),
+        };
+        check_contexts::<Pok<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let g = <C as CurveGroup>::Affine::rand(&mut rng);
+        let h = <C as CurveGroup>::Affine::rand(&mut rng);
+        let pp = RangeProof::<C>::generate_public_parameters(g, h, 8, &mut rng);
+        let witness = range::Witness::<C> {
+            values: vec![200],
+            gamma: vec![F::rand(&mut rng)],
+        };
+        let instance = range::Instance {
+            commitments: vec![RangeProof::commit(&pp, 200, witness.gamma[0])],
+            bits: 8,
+        };
+        let context = Context::new([0x11; 20], 1, 0);
+        let mut transcript = SHA3Base::new(true);
+        let proof = RangeProof::create_proof_with_context(
+            &pp,
+            &instance,
+            &witness,
+            Some(&context),
+            &mut transcript,
+            &mut rng,
+        )
+        .expect("proof failed");
+        let mut transcript = SHA3Base::new(true);
+        assert!(RangeProof::verify_proof_with_context(
+            &pp,
+            &instance,
+            &proof,
+            Some(&context),
+            &mut transcript
+        )
+        .unwrap());
+        let mut transcript = SHA3Base::new(true);
+        assert!(!matches!(
+            RangeProof::verify_proof_with_context(&pp, &instance, &proof, None, &mut transcript),
+            Ok(true)
+        ));
+    }
+
+    #[test]
+    fn merlin_transcript() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        type F = <C as ark_ec::Group>::ScalarField;
+
+        let l = 1 << *LOG_MIN;
+        let (pp, instance, witness) = linker_setup::<C, _>(l, *D0, *D1, *D2, &mut rng);
+        check_transcripts::<AmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_transcripts::<CompAmComEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let pp = am_eq_setup::<C, _>(*D1, &mut rng);
+        let (instance, witness) = am_eq_instance(&pp, l, &mut rng);
+        check_transcripts::<AmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+        check_transcripts::<CompAmEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let n = 11;
+        let pp = comp_dl_eq::PublicParameters::<C> {
+            g: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+            g_hat: (0..n)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+        let witness = comp_dl_eq::Witness::<C> {
+            z: (0..n).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = CompDLEq::<C>::evaluate(&pp, &witness.z).unwrap();
+        check_transcripts::<CompDLEq<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let pp = pok::PublicParameters::<C> {
+            ck: (0..*D0)
+                .map(|_| <C as CurveGroup>::Affine::rand(&mut rng))
+                .collect(),
+        };
+        let witness = pok::Witness {
+            opening: (0..*D0).map(|_| F::rand(&mut rng)).collect(),
+        };
+        let instance = pok::Instance {
+            commitment: Pedersen::<C>::commit(&pp.ck, &witness.opening),
+        };
+        check_transcripts::<Pok<C>, _>(&pp, &instance, &witness, &mut rng);
+
+        let g = <C as CurveGroup>::Affine::rand(&mut rng);
+        let h = <C as CurveGroup>::Affine::rand(&mut rng);
+        let pp = RangeProof::<C>::generate_public_parameters(g, h, 16, &mut rng);
+        let witness = range::Witness::<C> {
+            values: vec![42],
+            gamma: vec![F::rand(&mut rng)],
+        };
+        let instance = range::Instance {
+            commitments: vec![RangeProof::commit(&pp, 42, witness.gamma[0])],
+            bits: 16,
+        };
+        let mut transcript = MerlinTranscript::new(true);
+        let proof = RangeProof::create_proof(&pp, &instance, &witness, &mut transcript, &mut rng)
+            .expect("proof failed");
+        let mut transcript = MerlinTranscript::new(true);
+        assert!(RangeProof::verify_proof(&pp, &instance, &proof, &mut transcript).unwrap());
+        let mut transcript = SHA3Base::new(true);
+        assert!(!matches!(
+            RangeProof::verify_proof(&pp, &instance, &proof, &mut transcript),
+            Ok(true)
+        ));
+    }
 }
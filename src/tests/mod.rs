@@ -1,5 +1,9 @@
 mod circuit;
+mod commitment;
+mod link;
 mod linker;
+mod recursion;
+mod transcript;
 mod utils;
 
 use lazy_static::lazy_static;
@@ -0,0 +1,141 @@
+use ark_crypto_primitives::snark::FromFieldElementsGadget;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, eq::EqGadget, pairing::PairingVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef};
+use ark_std::{
+    marker::PhantomData,
+    rand::{CryptoRng, RngCore},
+};
+
+use super::circuit::{test_commitments, BatchCommitmentCircuit};
+
+use crate::{
+    crypto::protocol::transcript::{sha3::SHA3Base, TranscriptProtocol},
+    gro::{
+        constraints::{
+            BasePrimeField, CCGroth16VerifierGadget, InputVar, PreparedVerifyingKeyVar, ProofVar,
+        },
+        prepare_verifying_key, CCGroth16, PreparedVerifyingKey, Proof,
+    },
+    link::CpLink,
+    snark::{CircuitSpecificSetupCCSNARK, CCSNARK},
+};
+
+/// Verifies a proof over `E` in a circuit over `BasePrimeField<E>`
+struct RecursionCircuit<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> {
+    pvk: PreparedVerifyingKey<E>,
+    proof: Proof<E>,
+    public_inputs: Vec<E::ScalarField>,
+    _pairing: PhantomData<P>,
+}
+
+impl<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> Clone for RecursionCircuit<E, P> {
+    fn clone(&self) -> Self {
+        Self {
+            pvk: self.pvk.clone(),
+            proof: self.proof.clone(),
+            public_inputs: self.public_inputs.clone(),
+            _pairing: PhantomData,
+        }
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, BasePrimeField<E>>> ConstraintSynthesizer<BasePrimeField<E>>
+    for RecursionCircuit<E, P>
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<BasePrimeField<E>>,
+    ) -> ark_relations::r1cs::Result<()> {
+        let pvk = PreparedVerifyingKeyVar::<E, P>::new_constant(cs.clone(), self.pvk)?;
+        let proof = ProofVar::<E, P>::new_witness(cs.clone(), || Ok(self.proof))?;
+        let public_inputs = InputVar::<E>::new_input(cs, || Ok(self.public_inputs))?;
+
+        CCGroth16VerifierGadget::<E, P>::verify(&pvk, &proof, &public_inputs)?
+            .enforce_equal(&Boolean::TRUE)
+    }
+}
+
+/// Prove a batch commitment circuit over `E`, then prove its verification over `E2`,
+/// whose scalar field is the base field of `E`
+fn recursive_batch_commitment<E, E2, P, R>(batch_size: usize, rng: &mut R)
+where
+    E: Pairing,
+    E2: Pairing<ScalarField = BasePrimeField<E>>,
+    P: PairingVar<E, BasePrimeField<E>>,
+    R: RngCore + CryptoRng,
+{
+    let mock = BatchCommitmentCircuit::<E::G1>::mock(batch_size);
+    let (pk, vk, ck) = CCGroth16::<E>::setup(mock, 2, 2 + 2 * batch_size, rng).unwrap();
+    let pp = CpLink::<E>::batch_parameters(&ck).unwrap();
+
+    let commitments = test_commitments::<E::ScalarField>(batch_size, 2);
+    let messages = commitments.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let state =
+        CpLink::<E>::commit_batch(&ck, &commitments.concat(), &messages, &[], None, rng).unwrap();
+    let circuit = BatchCommitmentCircuit::<E::G1>::new(commitments.clone(), state.instance.tau);
+    let linked = CpLink::<E>::prove_batch(
+        &pk,
+        &pp,
+        circuit,
+        &state,
+        None,
+        &mut SHA3Base::new(false),
+        rng,
+    )
+    .unwrap();
+
+    // the aggregation is added to D ahead of the recursive verifier, as in `verify_batch`
+    let mut proof = linked.snark.clone();
+    proof.d = (proof.d + linked.link.c).into_affine();
+    let public_inputs = vec![state.instance.tau];
+    assert!(CCGroth16::<E>::verify(&vk, &public_inputs, &proof).unwrap());
+
+    let recursion = RecursionCircuit::<E, P> {
+        pvk: prepare_verifying_key(&vk),
+        proof: proof.clone(),
+        public_inputs: public_inputs.clone(),
+        _pairing: PhantomData,
+    };
+
+    // a proof without the aggregation does not satisfy the verifier
+    let cs = ConstraintSystem::new_ref();
+    RecursionCircuit::<E, P> {
+        proof: linked.snark,
+        ..recursion.clone()
+    }
+    .generate_constraints(cs.clone())
+    .unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+
+    let (outer_pk, outer_vk, outer_ck) =
+        CCGroth16::<E2>::setup(recursion.clone(), 0, 0, rng).unwrap();
+    let commitment = CCGroth16::<E2>::commit(&outer_ck, &[], rng).unwrap();
+    let outer_proof = CCGroth16::<E2>::prove(&outer_pk, recursion, &commitment, rng).unwrap();
+
+    let outer_inputs = InputVar::<E>::repack_input(&public_inputs);
+    assert!(CCGroth16::<E2>::verify(&outer_vk, &outer_inputs, &outer_proof).unwrap());
+
+    let mut tampered = outer_proof.clone();
+    tampered.a = (tampered.a + E2::G1Affine::generator()).into_affine();
+    assert!(!CCGroth16::<E2>::verify(&outer_vk, &outer_inputs, &tampered).unwrap());
+}
+
+pub mod bls12_377 {
+    use super::*;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        test_rng,
+    };
+
+    type E = ark_bls12_377::Bls12_377;
+    type E2 = ark_bw6_761::BW6_761;
+    type P = ark_bls12_377::constraints::PairingVar;
+    type R = StdRng;
+
+    #[test]
+    fn recursive_verification() {
+        let mut rng = R::seed_from_u64(test_rng().next_u64());
+        recursive_batch_commitment::<E, E2, P, _>(2, &mut rng);
+    }
+}
@@ -0,0 +1,166 @@
+pub mod bn254 {
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, uint8::UInt8, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::crypto::protocol::transcript::{
+        constraints::{PoseidonTranscriptVar, TranscriptGadget},
+        merlin::MerlinTranscript,
+        poseidon::PoseidonTranscript,
+        sha3::SHA3Base,
+        AppendToTranscript, Encoding, EncodingError, Points, TranscriptProtocol,
+    };
+
+    type F = ark_bn254::Fr;
+    type G = ark_bn254::G1Affine;
+
+    #[test]
+    fn merlin_conformance() {
+        // the simple transcript of the reference Merlin implementation
+        let mut transcript = MerlinTranscript::with_label(b"test protocol");
+        transcript.append(b"some label", b"some data");
+        let mut challenge = [0u8; 32];
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+
+        let hex = challenge
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        assert_eq!(
+            hex,
+            "d5a21972d0d5fe320c0d263fac7fffb8145aa640af6e9bca177c03c7efcf0615"
+        );
+    }
+
+    #[test]
+    fn merlin_framing() {
+        // raw concatenation cannot tell where a label ends and its item starts
+        let mut left = SHA3Base::new(true);
+        left.append(b"a", b"bc");
+        let mut right = SHA3Base::new(true);
+        right.append(b"ab", b"c");
+        assert_eq!(
+            left.challenge_scalar::<F>(b"challenge"),
+            right.challenge_scalar::<F>(b"challenge")
+        );
+
+        let mut left = MerlinTranscript::new(true);
+        left.append(b"a", b"bc");
+        let mut right = MerlinTranscript::new(true);
+        right.append(b"ab", b"c");
+        assert_ne!(
+            left.challenge_scalar::<F>(b"challenge"),
+            right.challenge_scalar::<F>(b"challenge")
+        );
+
+        // the output length is bound, so a shorter challenge is not a prefix of a longer one
+        let (mut short, mut long) = ([0u8; 32], [0u8; 64]);
+        MerlinTranscript::new(true).challenge_bytes(b"challenge", &mut short);
+        MerlinTranscript::new(true).challenge_bytes(b"challenge", &mut long);
+        assert_ne!(short[..], long[..32]);
+
+        // labels are absorbed whatever the flag
+        let mut labelled = MerlinTranscript::new(true);
+        let mut unlabelled = MerlinTranscript::new(false);
+        assert_eq!(
+            labelled.challenge_scalar::<F>(b"challenge"),
+            unlabelled.challenge_scalar::<F>(b"challenge")
+        );
+    }
+
+    #[test]
+    fn poseidon_gadget() {
+        let mut rng = test_rng();
+        let bytes = b"some data".to_vec();
+        let scalars = (0..3).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+
+        for use_label in [true, false] {
+            let mut transcript = PoseidonTranscript::<F>::new(use_label);
+            transcript.append(b"bytes", &bytes);
+            let first = transcript.challenge_scalar::<F>(b"challenge");
+            transcript.append_scalars(b"scalars", &scalars);
+            let second = transcript.challenge_scalar::<F>(b"challenge");
+
+            let cs = ConstraintSystem::<F>::new_ref();
+            let mut gadget: PoseidonTranscriptVar<F> = TranscriptGadget::new(cs.clone(), use_label);
+            let bytes_var = Vec::<UInt8<F>>::new_witness(cs.clone(), || Ok(bytes.clone())).unwrap();
+            let scalars_var =
+                Vec::<FpVar<F>>::new_witness(cs.clone(), || Ok(scalars.clone())).unwrap();
+            gadget.append(b"bytes", &bytes_var).unwrap();
+            let first_var = gadget.challenge_scalar(b"challenge").unwrap();
+            gadget.append_scalars(b"scalars", &scalars_var).unwrap();
+            let second_var = gadget.challenge_scalar(b"challenge").unwrap();
+
+            assert_eq!(first_var.value().unwrap(), first);
+            assert_eq!(second_var.value().unwrap(), second);
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        // elements of the field are not absorbed as their bytes
+        let mut native = PoseidonTranscript::<F>::new(true);
+        native.append_scalars(b"scalars", &scalars);
+        let mut serialized = PoseidonTranscript::<F>::new(true);
+        let bytes = scalars
+            .iter()
+            .flat_map(|s| s.into_bigint().to_bytes_le())
+            .collect::<Vec<_>>();
+        serialized.append(b"scalars", &bytes);
+        assert_ne!(
+            native.challenge_scalar::<F>(b"challenge"),
+            serialized.challenge_scalar::<F>(b"challenge")
+        );
+    }
+
+    #[test]
+    fn encodings() {
+        let mut rng = test_rng();
+        let point = (G::generator() * F::rand(&mut rng)).into_affine();
+        let scalar = F::rand(&mut rng);
+
+        // big-endian x || y, as abi.encodePacked lays out a point
+        let mut bytes = vec![];
+        point.encode(Encoding::Evm, &mut bytes).unwrap();
+        let (x, y) = point.xy().unwrap();
+        let expected = [x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()].concat();
+        assert_eq!(bytes, expected);
+
+        let mut bytes = vec![];
+        scalar.encode(Encoding::Evm, &mut bytes).unwrap();
+        assert_eq!(bytes, scalar.into_bigint().to_bytes_be());
+
+        // a vector is its elements one after the other, whichever the encoding
+        for encoding in [Encoding::Evm, Encoding::Canonical] {
+            let (mut vector, mut elements) = (vec![], vec![]);
+            (vec![point, point], scalar)
+                .encode(encoding, &mut vector)
+                .unwrap();
+            Points(&[point, point])
+                .encode(encoding, &mut elements)
+                .unwrap();
+            scalar.encode(encoding, &mut elements).unwrap();
+            assert_eq!(vector, elements);
+        }
+
+        // only the canonical encoding has the identity
+        let identity = G::zero();
+        assert_eq!(
+            identity.encode(Encoding::Evm, &mut vec![]),
+            Err(EncodingError::IdentityPoint)
+        );
+        assert!(identity.encode(Encoding::Canonical, &mut vec![]).is_ok());
+
+        // the encoding is chosen by the transcript
+        let mut evm = SHA3Base::new(true);
+        let mut canonical = SHA3Base::new(true).with_encoding(Encoding::Canonical);
+        point.append_to_transcript(b"point", &mut evm).unwrap();
+        point
+            .append_to_transcript(b"point", &mut canonical)
+            .unwrap();
+        assert_ne!(
+            evm.challenge_scalar::<F>(b"challenge"),
+            canonical.challenge_scalar::<F>(b"challenge")
+        );
+    }
+}